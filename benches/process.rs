@@ -0,0 +1,92 @@
+//! Compares the old per-sample processing path against the block based one.
+//!
+//! Run with `cargo bench`.
+
+#![feature(test)]
+
+extern crate test;
+
+use bit_flipper::model::BitParams;
+use bit_flipper_core::{Mode, Processor, Settings};
+use test::{Bencher, black_box};
+
+const BLOCK_SIZE: usize = 512;
+const MASK: u32 = 0x0000_f0f0;
+
+fn input() -> [Vec<f32>; 2] {
+    let channel = |phase: f32| -> Vec<f32> {
+        (0..BLOCK_SIZE)
+            .map(|i| (i as f32 * 0.05 + phase).sin() * 0.5)
+            .collect()
+    };
+
+    [channel(0.0), channel(1.0)]
+}
+
+/// Reads every mask parameter and transforms the samples one frame at a time, like `process()`
+/// used to.
+#[bench]
+fn per_sample(b: &mut Bencher) {
+    let params = BitParams::default();
+    let [mut left, mut right] = input();
+
+    b.iter(|| {
+        for (l, r) in left.iter_mut().zip(right.iter_mut()) {
+            let mask = params.to_u32() | MASK;
+
+            *l = Mode::Xor.transform(*l, mask);
+            *r = Mode::Xor.transform(*r, mask);
+        }
+
+        black_box((&left, &right));
+    });
+}
+
+/// Reads the mask once and transforms whole channels with SIMD.
+#[bench]
+fn block_simd(b: &mut Bencher) {
+    let params = BitParams::default();
+    let [mut left, mut right] = input();
+
+    b.iter(|| {
+        let mask = params.to_u32() | MASK;
+
+        Mode::Xor.transform_slice(&mut left, mask);
+        Mode::Xor.transform_slice(&mut right, mask);
+
+        black_box((&left, &right));
+    });
+}
+
+/// Reads the mask once and transforms whole channels converted to `f64`.
+#[bench]
+fn block_simd_f64(b: &mut Bencher) {
+    let params = BitParams::default();
+    let [mut left, mut right] = input();
+
+    b.iter(|| {
+        let mask = params.to_u64() | (MASK as u64) << 29;
+
+        Mode::Xor.transform_slice_f64(&mut left, mask);
+        Mode::Xor.transform_slice_f64(&mut right, mask);
+
+        black_box((&left, &right));
+    });
+}
+
+/// The complete processing chain with its default settings.
+#[bench]
+fn processor(b: &mut Bencher) {
+    let mut processor = Processor::new(48000.0);
+    let settings = Settings {
+        mask: MASK as u64,
+        ..Settings::default()
+    };
+    let [mut left, mut right] = input();
+
+    b.iter(|| {
+        processor.process(&mut [&mut left[..], &mut right[..]], None, &settings);
+
+        black_box((&left, &right));
+    });
+}
@@ -0,0 +1,420 @@
+//! Runs WAV files through the plugin's processing chain offline.
+//!
+//! ```text
+//! bit_flipper_cli [OPTIONS] <INPUT>...
+//! ```
+//!
+//! Every input may be a glob pattern, like `"samples/**/*.wav"`.
+
+use bit_flipper_core::preset::Preset;
+use bit_flipper_core::{MAX_CHANNELS, Mode, Processor, Settings};
+use hound::{SampleFormat, WavReader, WavSpec, WavWriter};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage: bit_flipper_cli [OPTIONS] <INPUT>...
+
+Options:
+  --preset <FILE>    Start from a preset saved by the plugin, the options after
+                     it change its settings
+  --mask <MASK>      Mask as 0x hex, 0b binary or decimal [default: 0]
+  --mode <MODE>      and, or, not or xor [default: xor]
+  --gain <DB>        Gain applied before the bit stages [default: 0]
+  --double           Flip the bits of the samples converted to f64
+  --bits <FORMAT>    Output format: 8, 16, 24, 32 or 32f [default: same as input]
+  --out-dir <DIR>    Where to write the results [default: next to the inputs]
+  --dry-run          Print peak, DC and NaN statistics instead of writing files
+  -h, --help         Print this help";
+
+/// Appended to the file name of every result written next to its input.
+const OUTPUT_SUFFIX: &str = "_flipped";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum OutputFormat {
+    Int(u16),
+    Float,
+}
+
+impl OutputFormat {
+    fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "8" | "16" | "24" | "32" => Ok(Self::Int(value.parse().unwrap())),
+            "32f" => Ok(Self::Float),
+            _ => Err(format!("unsupported output format '{value}'")),
+        }
+    }
+
+    fn of(spec: &WavSpec) -> Self {
+        match spec.sample_format {
+            SampleFormat::Int => Self::Int(spec.bits_per_sample),
+            SampleFormat::Float => Self::Float,
+        }
+    }
+
+    fn spec(&self, channels: u16, sample_rate: u32) -> WavSpec {
+        let (bits_per_sample, sample_format) = match *self {
+            Self::Int(bits) => (bits, SampleFormat::Int),
+            Self::Float => (32, SampleFormat::Float),
+        };
+
+        WavSpec {
+            channels,
+            sample_rate,
+            bits_per_sample,
+            sample_format,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Args {
+    settings: Settings,
+    format: Option<OutputFormat>,
+    out_dir: Option<PathBuf>,
+    dry_run: bool,
+    inputs: Vec<String>,
+}
+
+impl Args {
+    /// Returns `None` when the help was asked for.
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Option<Self>, String> {
+        let mut parsed = Self {
+            settings: Settings::default(),
+            format: None,
+            out_dir: None,
+            dry_run: false,
+            inputs: Vec::new(),
+        };
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("missing value for {arg}"))
+            };
+
+            match arg.as_str() {
+                "--preset" => parsed.settings = load_preset(Path::new(&value()?))?,
+                "--mask" => parsed.settings.mask = parse_mask(&value()?)?,
+                "--mode" => parsed.settings.mode = parse_mode(&value()?)?,
+                "--gain" => {
+                    let db: f32 = value()?
+                        .parse()
+                        .map_err(|_| "gain needs to be a number".to_string())?;
+
+                    parsed.settings.pre_gain = 10f32.powf(db / 20.0);
+                }
+                "--double" => parsed.settings.double_precision = true,
+                "--bits" => parsed.format = Some(OutputFormat::parse(&value()?)?),
+                "--out-dir" => parsed.out_dir = Some(value()?.into()),
+                "--dry-run" => parsed.dry_run = true,
+                "-h" | "--help" => return Ok(None),
+                _ if arg.starts_with('-') => return Err(format!("unknown option '{arg}'")),
+                _ => parsed.inputs.push(arg),
+            }
+        }
+
+        if parsed.inputs.is_empty() {
+            return Err(USAGE.to_string());
+        }
+
+        Ok(Some(parsed))
+    }
+}
+
+/// Reads a preset saved by the plugin into settings. Synced freezes assume 120 BPM.
+fn load_preset(path: &Path) -> Result<Settings, String> {
+    let json = std::fs::read_to_string(path).map_err(|err| format!("{}: {err}", path.display()))?;
+
+    Preset::from_json(&json)
+        .and_then(|preset| preset.settings())
+        .map_err(|err| format!("{}: {err}", path.display()))
+}
+
+fn parse_mask(value: &str) -> Result<u64, String> {
+    let value = value.replace('_', "");
+    let parsed = if let Some(hex) = value.strip_prefix("0x") {
+        u64::from_str_radix(hex, 16)
+    } else if let Some(bin) = value.strip_prefix("0b") {
+        u64::from_str_radix(bin, 2)
+    } else {
+        value.parse()
+    };
+
+    parsed.map_err(|_| format!("invalid mask '{value}'"))
+}
+
+fn parse_mode(value: &str) -> Result<Mode, String> {
+    match value.to_lowercase().as_str() {
+        "and" => Ok(Mode::And),
+        "or" => Ok(Mode::Or),
+        "not" => Ok(Mode::Not),
+        "xor" => Ok(Mode::Xor),
+        _ => Err(format!("unknown mode '{value}'")),
+    }
+}
+
+/// Reads every channel of the file, normalized to `-1.0..=1.0`.
+fn read(path: &Path) -> Result<(WavSpec, Vec<Vec<f32>>), String> {
+    let reader = WavReader::open(path).map_err(|err| err.to_string())?;
+    let spec = reader.spec();
+
+    if spec.channels as usize > MAX_CHANNELS {
+        return Err(format!("{} channels are not supported", spec.channels));
+    }
+
+    let samples: Vec<f32> = match spec.sample_format {
+        SampleFormat::Float => reader.into_samples::<f32>().collect::<Result<_, _>>(),
+        SampleFormat::Int => reader
+            .into_samples::<i32>()
+            .map(|sample| sample.map(|sample| int_to_float(sample, spec.bits_per_sample)))
+            .collect::<Result<_, _>>(),
+    }
+    .map_err(|err| err.to_string())?;
+
+    let mut channels = vec![Vec::with_capacity(samples.len()); spec.channels as usize];
+    for frame in samples.chunks(spec.channels as usize) {
+        for (channel, sample) in channels.iter_mut().zip(frame) {
+            channel.push(*sample);
+        }
+    }
+
+    Ok((spec, channels))
+}
+
+/// The value of full scale for `bits` bit integer samples. Both directions use the same scale so
+/// samples survive a round trip unchanged.
+fn int_scale(bits: u16) -> f32 {
+    (1u64 << (bits - 1)) as f32
+}
+
+fn int_to_float(sample: i32, bits: u16) -> f32 {
+    sample as f32 / int_scale(bits)
+}
+
+/// Full scale positive samples end up one step below full scale, there's no integer for them.
+fn float_to_int(sample: f32, bits: u16) -> i32 {
+    let scale = int_scale(bits);
+
+    (sample * scale).round().clamp(-scale, scale - 1.0) as i32
+}
+
+fn write(path: &Path, spec: WavSpec, channels: &[Vec<f32>]) -> Result<(), String> {
+    let mut writer = WavWriter::create(path, spec).map_err(|err| err.to_string())?;
+    let len = channels.first().map_or(0, Vec::len);
+
+    for frame in 0..len {
+        for channel in channels {
+            // NaNs end up as silence
+            let sample = match channel[frame].is_nan() {
+                true => 0.0,
+                false => channel[frame].clamp(-1.0, 1.0),
+            };
+
+            let result = match spec.sample_format {
+                SampleFormat::Float => writer.write_sample(sample),
+                SampleFormat::Int => {
+                    writer.write_sample(float_to_int(sample, spec.bits_per_sample))
+                }
+            };
+
+            result.map_err(|err| err.to_string())?;
+        }
+    }
+
+    writer.finalize().map_err(|err| err.to_string())
+}
+
+fn print_statistics(path: &Path, channels: &[Vec<f32>]) {
+    println!("{}", path.display());
+
+    for (index, channel) in channels.iter().enumerate() {
+        let nans = channel.iter().filter(|sample| sample.is_nan()).count();
+        let finite = channel.iter().filter(|sample| sample.is_finite());
+
+        let peak = finite
+            .clone()
+            .fold(0.0f32, |peak, sample| peak.max(sample.abs()));
+        let dc = finite.map(|sample| *sample as f64).sum::<f64>() / channel.len().max(1) as f64;
+
+        println!(
+            "  channel {index}: peak {:.2} dBFS, DC {dc:+.5}, NaN {nans}",
+            20.0 * peak.max(f32::MIN_POSITIVE).log10(),
+        );
+    }
+}
+
+fn output_path(input: &Path, out_dir: Option<&Path>) -> PathBuf {
+    let stem = input.file_stem().unwrap_or_default().to_string_lossy();
+    let name = format!("{stem}{OUTPUT_SUFFIX}.wav");
+
+    match out_dir {
+        Some(dir) => dir.join(name),
+        None => input.with_file_name(name),
+    }
+}
+
+fn process_file(path: &Path, args: &Args) -> Result<(), String> {
+    let (spec, mut channels) = read(path)?;
+
+    // Surround WAV files put the LFE channel fourth, like the plugin's surround layouts
+    let settings = Settings {
+        lfe_channel: (spec.channels >= 6).then_some(3),
+        ..args.settings
+    };
+
+    let mut processor = Processor::new(spec.sample_rate as f32);
+
+    // The limiter's lookahead delays the output. Run silence through to get the end of the file
+    // out, then drop the delay from the start, so the result lines up with the input.
+    let latency = processor.latency(&settings);
+    for channel in &mut channels {
+        channel.resize(channel.len() + latency, 0.0);
+    }
+
+    let mut slices: Vec<&mut [f32]> = channels.iter_mut().map(Vec::as_mut_slice).collect();
+    processor.process(&mut slices, None, &settings);
+
+    for channel in &mut channels {
+        channel.drain(..latency);
+    }
+
+    if args.dry_run {
+        print_statistics(path, &channels);
+        return Ok(());
+    }
+
+    let format = args.format.unwrap_or_else(|| OutputFormat::of(&spec));
+    let output = output_path(path, args.out_dir.as_deref());
+
+    write(
+        &output,
+        format.spec(spec.channels, spec.sample_rate),
+        &channels,
+    )?;
+    println!("{} -> {}", path.display(), output.display());
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args = match Args::parse(std::env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(message) => {
+            eprintln!("{message}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if let Some(dir) = &args.out_dir
+        && let Err(err) = std::fs::create_dir_all(dir)
+    {
+        eprintln!("{}: {err}", dir.display());
+        return ExitCode::FAILURE;
+    }
+
+    let mut failed = false;
+
+    for pattern in &args.inputs {
+        let paths = match glob::glob(pattern) {
+            Ok(paths) => paths,
+            Err(err) => {
+                eprintln!("{pattern}: {err}");
+                failed = true;
+                continue;
+            }
+        };
+
+        let mut matched = false;
+
+        for path in paths {
+            matched = true;
+
+            let result = path.map_err(|err| err.to_string()).and_then(|path| {
+                process_file(&path, &args).map_err(|err| format!("{}: {err}", path.display()))
+            });
+
+            if let Err(err) = result {
+                eprintln!("{err}");
+                failed = true;
+            }
+        }
+
+        if !matched {
+            eprintln!("no files matched {pattern}");
+            failed = true;
+        }
+    }
+
+    match failed {
+        true => ExitCode::FAILURE,
+        false => ExitCode::SUCCESS,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn masks_parse_in_every_base() {
+        assert_eq!(parse_mask("0x1f"), Ok(0x1f));
+        assert_eq!(parse_mask("0b101"), Ok(0b101));
+        assert_eq!(parse_mask("42"), Ok(42));
+        assert_eq!(parse_mask("0xffff_ffff"), Ok(0xffff_ffff));
+        assert_eq!(parse_mask("0xffffffffffffffff"), Ok(u64::MAX));
+
+        assert!(parse_mask("0x1_0000_0000_0000_0000").is_err());
+        assert!(parse_mask("0b102").is_err());
+        assert!(parse_mask("-1").is_err());
+        assert!(parse_mask("").is_err());
+    }
+
+    #[test]
+    fn output_formats_parse() {
+        assert_eq!(OutputFormat::parse("8"), Ok(OutputFormat::Int(8)));
+        assert_eq!(OutputFormat::parse("16"), Ok(OutputFormat::Int(16)));
+        assert_eq!(OutputFormat::parse("24"), Ok(OutputFormat::Int(24)));
+        assert_eq!(OutputFormat::parse("32"), Ok(OutputFormat::Int(32)));
+        assert_eq!(OutputFormat::parse("32f"), Ok(OutputFormat::Float));
+
+        assert!(OutputFormat::parse("12").is_err());
+        assert!(OutputFormat::parse("64f").is_err());
+    }
+
+    #[test]
+    fn int_samples_survive_a_round_trip() {
+        for bits in [8u16, 16, 24, 32] {
+            let (min, max) = (-(1i64 << (bits - 1)), (1i64 << (bits - 1)) - 1);
+            // Some 65536 samples per depth. Larger steps also keep 32-bit samples within the 24
+            // bits an f32 holds exactly.
+            let step = 1usize << bits.saturating_sub(16);
+
+            let samples = (min..=max).step_by(step).chain([-1, 0, 1, max]);
+
+            for sample in samples {
+                let float = int_to_float(sample as i32, bits);
+
+                assert!(
+                    (-1.0..=1.0).contains(&float),
+                    "{bits} bits: {sample} -> {float}"
+                );
+                assert_eq!(float_to_int(float, bits), sample as i32, "{bits} bits");
+            }
+        }
+    }
+
+    #[test]
+    fn float_samples_are_clamped_to_the_int_range() {
+        for bits in [8, 16, 24, 32] {
+            let max = ((1i64 << (bits - 1)) - 1) as i32;
+
+            assert_eq!(float_to_int(1.0, bits), max, "{bits} bits");
+            assert_eq!(float_to_int(-1.0, bits), -max - 1, "{bits} bits");
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use super::{MAX_BLOCK_SIZE, MAX_CHANNELS, MAX_LOOKAHEAD};
+
+use core::ops::Range;
+
+/// How long switching the bypass on or off takes.
+pub const BYPASS_FADE_MS: f32 = 5.0;
+
+/// Room for the dry signal delayed by the full chain latency.
+const DELAY_SIZE: usize = MAX_LOOKAHEAD + 1;
+
+/// Crossfades between the processed signal and the input, delayed by the chain's latency so both
+/// stay aligned.
+#[derive(Debug, Clone)]
+pub struct Bypass {
+    /// Runs from `0.0`, fully processed, to `1.0`, fully bypassed.
+    mix: f32,
+    delay: [[f32; DELAY_SIZE]; MAX_CHANNELS],
+    pos: usize,
+}
+
+impl Default for Bypass {
+    fn default() -> Self {
+        Self {
+            mix: 0.0,
+            delay: [[0.0; DELAY_SIZE]; MAX_CHANNELS],
+            pos: 0,
+        }
+    }
+}
+
+impl Bypass {
+    /// Clears the delayed input. A bypass that is already engaged stays engaged.
+    pub fn reset(&mut self) {
+        *self = Self {
+            mix: self.mix,
+            ..Self::default()
+        };
+    }
+
+    /// Mixes `input`, the unprocessed block, into `range` of the processed `channels`. `step` is
+    /// how far the mix moves per sample.
+    pub fn process(
+        &mut self,
+        channels: &mut [&mut [f32]],
+        input: &[[f32; MAX_BLOCK_SIZE]],
+        range: Range<usize>,
+        latency: usize,
+        bypassed: bool,
+        step: f32,
+    ) {
+        let target = if bypassed { 1.0 } else { 0.0 };
+        let read_offset = DELAY_SIZE - latency.min(MAX_LOOKAHEAD);
+
+        for (index, frame) in range.enumerate() {
+            self.mix = match bypassed {
+                true => (self.mix + step).min(target),
+                false => (self.mix - step).max(target),
+            };
+
+            let read = (self.pos + read_offset) % DELAY_SIZE;
+
+            for ((samples, delay), input) in channels.iter_mut().zip(&mut self.delay).zip(input) {
+                delay[self.pos] = input[index];
+
+                let dry = delay[read];
+                samples[frame] += (dry - samples[frame]) * self.mix;
+            }
+
+            self.pos = (self.pos + 1) % DELAY_SIZE;
+        }
+    }
+}
@@ -0,0 +1,17 @@
+/// A setting with a fixed list of options. Presets and the plugin's choice parameters store the
+/// index of the option, so the order of [`OPTIONS`][Self::OPTIONS] can't change.
+pub trait Choice: Copy + Default + PartialEq + 'static {
+    const OPTIONS: &'static [Self];
+
+    fn index(self) -> usize {
+        Self::OPTIONS
+            .iter()
+            .position(|option| *option == self)
+            .unwrap_or_default()
+    }
+
+    /// The option at `index`, or the default one when there's no such option.
+    fn from_index(index: usize) -> Self {
+        Self::OPTIONS.get(index).copied().unwrap_or_default()
+    }
+}
@@ -0,0 +1,46 @@
+use core::f32::consts::TAU;
+
+/// Sample-and-hold rate reducer with an optional anti-aliasing pre-filter.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Decimator {
+    /// Samples left until the next value gets captured.
+    countdown: f32,
+    held: f32,
+    /// State of the two cascaded one-pole low-pass filters.
+    lowpass: [f32; 2],
+}
+
+impl Decimator {
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// `rate` is the amount of input samples per held sample and may be fractional.
+    pub fn process(&mut self, sample: f32, rate: f32, filter: bool) -> f32 {
+        let input = if filter && rate > 1.0 {
+            let coeff = lowpass_coefficient(rate);
+
+            self.lowpass[0] += coeff * (sample - self.lowpass[0]);
+            self.lowpass[1] += coeff * (self.lowpass[0] - self.lowpass[1]);
+            self.lowpass[1]
+        } else {
+            sample
+        };
+
+        if self.countdown <= 0.0 {
+            self.held = input;
+            self.countdown += rate.max(1.0);
+        }
+
+        self.countdown -= 1.0;
+        self.held
+    }
+}
+
+/// One-pole coefficient placing the cutoff slightly below the Nyquist frequency of the reduced
+/// rate. The cutoff is relative to the host sample rate, so the latter is not needed here.
+fn lowpass_coefficient(rate: f32) -> f32 {
+    let cutoff = 0.45 / rate.max(1.0);
+
+    1.0 - libm::expf(-TAU * cutoff)
+}
@@ -0,0 +1,32 @@
+//! The default value of every parameter, as plain values. The plugin starts its parameters out at
+//! these, and presets fall back to them for the parameters they leave out.
+//!
+//! Choices default to the [`Default`] of their type, toggles not listed here default to off.
+
+pub const PRE_GAIN: f32 = 1.0;
+
+pub const GENERATOR_FREQUENCY: f32 = 440.0;
+pub const GENERATOR_LEVEL_DB: f32 = -12.0;
+pub const GENERATOR_REPLACE: bool = true;
+
+pub const DECIMATE_RATE: f32 = 1.0;
+pub const DECIMATE_FILTER: bool = true;
+
+pub const TRUNCATE_DEPTH: u32 = 23;
+
+pub const FREEZE_TIME_MS: f32 = 50.0;
+/// An index into [`FREEZE_DIVISIONS`][crate::FREEZE_DIVISIONS], a sixteenth note.
+pub const FREEZE_DIVISION: usize = 4;
+
+pub const TRANSITION_TIME_MS: f32 = 10.0;
+
+pub const HISTORY_DELAY: usize = 1;
+pub const HISTORY_RESET: usize = 256;
+
+pub const GATE_THRESHOLD_DB: f32 = -60.0;
+pub const GATE_HYSTERESIS_DB: f32 = 6.0;
+pub const GATE_ATTACK_MS: f32 = 1.0;
+pub const GATE_RELEASE_MS: f32 = 100.0;
+
+pub const LIMITER_CEILING_DB: f32 = -1.0;
+pub const LIMITER_RELEASE_MS: f32 = 100.0;
@@ -0,0 +1,76 @@
+/// The lengths of the note divisions a synced freeze can be set to, in quarter notes. Longest
+/// first, from a whole note down to a thirty-second note.
+pub const FREEZE_DIVISIONS: [f32; 6] = [4.0, 2.0, 1.0, 0.5, 0.25, 0.125];
+
+/// How long frozen bits are held.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FreezeLength {
+    Milliseconds(f32),
+    /// Synced to the host tempo, in quarter notes.
+    Beats(f32),
+}
+
+impl FreezeLength {
+    /// Tempo used for synced lengths while the host doesn't report one.
+    const FALLBACK_TEMPO: f32 = 120.0;
+
+    pub fn samples(&self, sample_rate: f32, tempo: Option<f32>) -> f32 {
+        let ms = match *self {
+            Self::Milliseconds(ms) => ms,
+            Self::Beats(beats) => beats * 60_000.0 / tempo.unwrap_or(Self::FALLBACK_TEMPO),
+        };
+
+        (ms / 1000.0 * sample_rate).max(1.0)
+    }
+}
+
+/// Latches the masked bits of a captured sample while the other bits pass through the transform.
+/// The held bits skip the transform, otherwise it would combine them with the mask all over again.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Freeze {
+    /// Samples left until the next value gets captured.
+    countdown: f32,
+    held: u64,
+    /// The timeline position the next block starts at, after a restart. The first hold period
+    /// gets shortened so the following ones start on multiples of the length.
+    position: Option<i64>,
+}
+
+impl Freeze {
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Starts over at `position` on the timeline, in samples.
+    pub fn restart(&mut self, position: i64) {
+        *self = Self {
+            position: Some(position),
+            ..Self::default()
+        };
+    }
+
+    /// Captures from `input`, the bits going into the transform, and puts the held bits in place
+    /// of the masked bits of `output`, the transformed ones. `length` is the hold time in samples
+    /// and may be fractional.
+    pub fn process(&mut self, input: u64, output: u64, mask: u64, length: f32) -> u64 {
+        if let Some(position) = self.position.take() {
+            // Positions before the start of the timeline count backwards from it
+            let length_f64 = length as f64;
+            let elapsed = libm::fmod(
+                libm::fmod(position as f64, length_f64) + length_f64,
+                length_f64,
+            );
+            let elapsed = elapsed as f32;
+
+            self.held = input;
+            self.countdown = length - elapsed;
+        } else if self.countdown <= 0.0 {
+            self.held = input;
+            self.countdown += length;
+        }
+
+        self.countdown -= 1.0;
+
+        (output & !mask) | (self.held & mask)
+    }
+}
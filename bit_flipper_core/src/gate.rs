@@ -0,0 +1,92 @@
+use super::{Choice, db_to_gain, smoothing_coefficient};
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum GateAction {
+    /// Let the unprocessed input through while the gate is closed.
+    #[default]
+    Bypass,
+    /// Fade the output to silence while the gate is closed.
+    Mute,
+}
+
+impl Choice for GateAction {
+    const OPTIONS: &'static [Self] = &[Self::Bypass, Self::Mute];
+}
+
+/// Release time of the level detector, long enough to ride over the waveform's zero crossings.
+const DETECTOR_RELEASE_MS: f32 = 10.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GateSettings {
+    pub threshold_db: f32,
+    pub hysteresis_db: f32,
+    pub attack_ms: f32,
+    pub release_ms: f32,
+    pub action: GateAction,
+    /// Key the gate from the sidechain input, if there is one.
+    pub sidechain: bool,
+}
+
+/// Gate coefficients, derived from the [`GateSettings`] once per block.
+#[derive(Debug, Clone, Copy)]
+pub struct GateCoefficients {
+    open_threshold: f32,
+    close_threshold: f32,
+    attack: f32,
+    release: f32,
+    detector: f32,
+}
+
+impl GateCoefficients {
+    pub fn new(settings: &GateSettings, sample_rate: f32) -> Self {
+        let threshold_db = settings.threshold_db;
+
+        Self {
+            open_threshold: db_to_gain(threshold_db),
+            close_threshold: db_to_gain(threshold_db - settings.hysteresis_db),
+            attack: smoothing_coefficient(settings.attack_ms, sample_rate),
+            release: smoothing_coefficient(settings.release_ms, sample_rate),
+            detector: smoothing_coefficient(DETECTOR_RELEASE_MS, sample_rate),
+        }
+    }
+}
+
+/// Threshold gate with hysteresis. It only computes a gain, applying it is up to the caller.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Gate {
+    envelope: f32,
+    open: bool,
+    gain: f32,
+}
+
+impl Gate {
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Feeds the peak level of the current sample frame and returns the gate's gain for it.
+    pub fn process(&mut self, level: f32, coefficients: &GateCoefficients) -> f32 {
+        self.envelope = match level > self.envelope {
+            true => level,
+            false => self.envelope + (level - self.envelope) * coefficients.detector,
+        };
+
+        if self.envelope > coefficients.open_threshold {
+            self.open = true;
+        } else if self.envelope < coefficients.close_threshold {
+            self.open = false;
+        }
+
+        let (target, coeff) = match self.open {
+            true => (1.0, coefficients.attack),
+            false => (0.0, coefficients.release),
+        };
+
+        self.gain += (target - self.gain) * coeff;
+        self.gain
+    }
+}
@@ -0,0 +1,153 @@
+use super::Choice;
+
+use core::f32::consts::TAU;
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum Waveform {
+    #[default]
+    Sine,
+    Saw,
+    Square,
+    WhiteNoise,
+    PinkNoise,
+    /// Sine sweep across the audible range. Ignores the frequency.
+    Sweep,
+}
+
+impl Choice for Waveform {
+    const OPTIONS: &'static [Self] = &[
+        Self::Sine,
+        Self::Saw,
+        Self::Square,
+        Self::WhiteNoise,
+        Self::PinkNoise,
+        Self::Sweep,
+    ];
+}
+
+/// Lowest and highest frequency of [`Waveform::Sweep`].
+const SWEEP_RANGE: (f32, f32) = (20.0, 20_000.0);
+const SWEEP_SECONDS: f32 = 4.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeneratorSettings {
+    pub waveform: Waveform,
+    pub frequency: f32,
+    /// Linear gain of the generated signal.
+    pub level: f32,
+    /// Replace the input instead of mixing the signal into it.
+    pub replace: bool,
+}
+
+/// A small xorshift generator, so the audio thread never has to touch shared RNG state.
+#[derive(Debug, Clone, Copy)]
+pub struct Rng(u32);
+
+impl Default for Rng {
+    fn default() -> Self {
+        Self(0x9e37_79b9)
+    }
+}
+
+impl Rng {
+    pub fn new(seed: u32) -> Self {
+        // Zero is the one state xorshift can never leave
+        Self(seed.max(1))
+    }
+
+    /// Scrambles a position on the timeline into a seed, so neighbouring positions still produce
+    /// unrelated sequences.
+    pub fn from_position(position: i64) -> Self {
+        let mut x = (position as u64).wrapping_add(0x9e37_79b9_7f4a_7c15);
+        x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+
+        Self::new((x ^ (x >> 31)) as u32)
+    }
+
+    /// A uniformly distributed value between `-1.0` and `1.0`.
+    pub fn next_bipolar(&mut self) -> f32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 17;
+        self.0 ^= self.0 << 5;
+
+        (self.0 >> 8) as f32 / (1 << 23) as f32 - 1.0
+    }
+}
+
+/// Test signal oscillator for auditioning settings without any input.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Generator {
+    /// Runs from `0.0` to `1.0` over one period.
+    phase: f32,
+    /// Position within the sweep, in seconds.
+    sweep_time: f32,
+    rng: Rng,
+    /// State of the pink noise filter bank.
+    pink: [f32; 7],
+}
+
+impl Generator {
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Starts over with a noise sequence that only depends on `position`.
+    pub fn restart(&mut self, position: i64) {
+        *self = Self {
+            rng: Rng::from_position(position),
+            ..Self::default()
+        };
+    }
+
+    pub fn next(&mut self, settings: &GeneratorSettings, sample_rate: f32) -> f32 {
+        let value = match settings.waveform {
+            Waveform::Sine => libm::sinf(self.phase * TAU),
+            Waveform::Saw => self.phase * 2.0 - 1.0,
+            Waveform::Square => match self.phase < 0.5 {
+                true => 1.0,
+                false => -1.0,
+            },
+            Waveform::WhiteNoise => self.rng.next_bipolar(),
+            Waveform::PinkNoise => self.next_pink(),
+            Waveform::Sweep => libm::sinf(self.phase * TAU),
+        };
+
+        let frequency = match settings.waveform {
+            Waveform::Sweep => self.advance_sweep(sample_rate),
+            _ => settings.frequency,
+        };
+        let phase = self.phase + frequency / sample_rate;
+        self.phase = phase - libm::truncf(phase);
+
+        value * settings.level
+    }
+
+    /// Exponential sweep across [`SWEEP_RANGE`] that starts over every [`SWEEP_SECONDS`].
+    fn advance_sweep(&mut self, sample_rate: f32) -> f32 {
+        let (low, high) = SWEEP_RANGE;
+        let frequency = low * libm::powf(high / low, self.sweep_time / SWEEP_SECONDS);
+
+        self.sweep_time = (self.sweep_time + 1.0 / sample_rate) % SWEEP_SECONDS;
+
+        frequency
+    }
+
+    /// Paul Kellet's refined pink noise filter, scaled to roughly the same peak as white noise.
+    fn next_pink(&mut self) -> f32 {
+        let white = self.rng.next_bipolar();
+        let b = &mut self.pink;
+
+        b[0] = 0.99886 * b[0] + white * 0.0555179;
+        b[1] = 0.99332 * b[1] + white * 0.0750759;
+        b[2] = 0.96900 * b[2] + white * 0.153852;
+        b[3] = 0.86650 * b[3] + white * 0.3104856;
+        b[4] = 0.55000 * b[4] + white * 0.5329522;
+        b[5] = -0.7616 * b[5] - white * 0.0168980;
+
+        let pink = b[0] + b[1] + b[2] + b[3] + b[4] + b[5] + b[6] + white * 0.5362;
+        b[6] = white * 0.115926;
+
+        pink * 0.11
+    }
+}
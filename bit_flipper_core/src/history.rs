@@ -0,0 +1,84 @@
+use super::Choice;
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum HistoryMode {
+    /// Use the sample from a fixed amount of frames ago.
+    #[default]
+    Delayed,
+    /// Use the bits all samples since the last reset have in common.
+    AndAccumulator,
+    /// Use the bits any sample since the last reset had set.
+    OrAccumulator,
+}
+
+impl Choice for HistoryMode {
+    const OPTIONS: &'static [Self] = &[Self::Delayed, Self::AndAccumulator, Self::OrAccumulator];
+}
+
+/// Longest delay the history stage can look back, in frames.
+pub const MAX_HISTORY: usize = 2048;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HistorySettings {
+    pub mode: HistoryMode,
+    /// How many frames back [`HistoryMode::Delayed`] looks.
+    pub delay: usize,
+    /// Amount of samples after which the accumulators start over.
+    pub reset: usize,
+}
+
+/// Derives a mask from the bits of the previous samples of one channel.
+#[derive(Debug, Clone)]
+pub struct History {
+    /// Ring buffer with the bits of the last [`MAX_HISTORY`] samples.
+    buffer: [u64; MAX_HISTORY],
+    position: usize,
+    accumulator: u64,
+    /// Samples accumulated since the last reset.
+    accumulated: usize,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self {
+            buffer: [0; MAX_HISTORY],
+            position: 0,
+            accumulator: 0,
+            accumulated: 0,
+        }
+    }
+}
+
+impl History {
+    pub fn reset(&mut self) {
+        self.buffer.fill(0);
+        self.position = 0;
+        self.accumulator = 0;
+        self.accumulated = 0;
+    }
+
+    /// Returns the mask for the sample with these `bits` and remembers them for later samples.
+    pub fn process(&mut self, bits: u64, settings: &HistorySettings) -> u64 {
+        let delay = settings.delay.clamp(1, MAX_HISTORY);
+        let delayed = self.buffer[(self.position + MAX_HISTORY - delay) % MAX_HISTORY];
+
+        self.buffer[self.position] = bits;
+        self.position = (self.position + 1) % MAX_HISTORY;
+
+        let mask = match settings.mode {
+            HistoryMode::Delayed => return delayed,
+            // The first sample of every period only starts the accumulator and stays untouched
+            _ if self.accumulated == 0 => 0,
+            _ => self.accumulator,
+        };
+
+        self.accumulator = match settings.mode {
+            _ if self.accumulated == 0 => bits,
+            HistoryMode::OrAccumulator => self.accumulator | bits,
+            _ => self.accumulator & bits,
+        };
+        self.accumulated = (self.accumulated + 1) % settings.reset.max(1);
+
+        mask
+    }
+}
@@ -0,0 +1,78 @@
+//! The bit flipping effect on its own: `no_std`, allocation free, and without any plugin or GPU
+//! dependencies.
+//!
+//! [`Processor`] runs the complete chain on a set of channel slices. When only the mask transform
+//! is needed, [`process_slice()`] applies it to a single slice.
+//!
+//! The parameter [`defaults`] and the [`preset`] format live here too, so the plugin and the
+//! command line tool agree on them. The format needs the `preset` feature, which uses `alloc`.
+//!
+//! The `simd` feature transforms slices with `core::simd`, which needs a nightly compiler. Without
+//! it everything builds on stable.
+
+#![no_std]
+#![cfg_attr(feature = "simd", feature(portable_simd))]
+
+#[cfg(feature = "preset")]
+extern crate alloc;
+
+mod bypass;
+mod choice;
+mod decimator;
+mod freeze;
+mod gate;
+mod generator;
+mod history;
+mod limiter;
+mod macros;
+mod mode;
+mod processor;
+mod transform;
+mod transition;
+mod truncate;
+
+pub mod defaults;
+#[cfg(feature = "preset")]
+pub mod preset;
+
+pub use bypass::*;
+pub use choice::*;
+pub use decimator::*;
+pub use freeze::*;
+pub use gate::*;
+pub use generator::*;
+pub use history::*;
+pub use limiter::*;
+pub use macros::*;
+pub use mode::*;
+pub use processor::*;
+pub use transform::*;
+pub use transition::*;
+pub use truncate::*;
+
+/// The most channels the processor handles at once, enough for 7.1.
+pub const MAX_CHANNELS: usize = 8;
+
+/// Bit `n` of a mask applies to bit `n` of a sample. Single precision only uses the lower 32 bits.
+pub type Mask = u64;
+
+/// Combines every sample with the mask in place, without any of the other stages.
+pub fn process_slice(samples: &mut [f32], mask: Mask, mode: Mode) {
+    Transform {
+        mask,
+        mode,
+        ..Transform::default()
+    }
+    .apply_slice(samples);
+}
+
+pub fn db_to_gain(db: f32) -> f32 {
+    libm::powf(10.0, db / 20.0)
+}
+
+/// One-pole coefficient that covers roughly two thirds of a step within `time_ms`.
+pub fn smoothing_coefficient(time_ms: f32, sample_rate: f32) -> f32 {
+    let samples = (time_ms / 1000.0 * sample_rate).max(1.0);
+
+    1.0 - libm::expf(-1.0 / samples)
+}
@@ -0,0 +1,160 @@
+use super::{MAX_CHANNELS, db_to_gain, smoothing_coefficient};
+
+/// Upper bound for the lookahead, enough for [`LOOKAHEAD_MS`] at 384 kHz.
+pub const MAX_LOOKAHEAD: usize = 1024;
+pub const LOOKAHEAD_MS: f32 = 2.0;
+
+/// Amount of points estimated between two samples when looking for inter-sample peaks.
+const OVERSAMPLING: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LimiterSettings {
+    pub ceiling_db: f32,
+    pub release_ms: f32,
+}
+
+/// Limiter coefficients, derived from the [`LimiterSettings`] once per block.
+#[derive(Debug, Clone, Copy)]
+pub struct LimiterCoefficients {
+    ceiling: f32,
+    release: f32,
+}
+
+impl LimiterCoefficients {
+    pub fn new(settings: &LimiterSettings, sample_rate: f32) -> Self {
+        Self {
+            ceiling: db_to_gain(settings.ceiling_db),
+            release: smoothing_coefficient(settings.release_ms, sample_rate),
+        }
+    }
+}
+
+/// Stereo-linked true-peak limiter with a fixed lookahead.
+///
+/// The gain computer holds the lowest required gain over the lookahead window and one sample more,
+/// lets it recover with an exponential release, and then averages it over the lookahead window.
+/// The average is guaranteed to have fully reached the required gain by the time the delayed peak
+/// comes out.
+#[derive(Debug, Clone)]
+pub struct Limiter {
+    lookahead: usize,
+    pos: usize,
+
+    delay: [[f32; MAX_LOOKAHEAD]; MAX_CHANNELS],
+    /// The last two input samples of every channel, for the inter-sample peak estimation.
+    history: [[f32; 2]; MAX_CHANNELS],
+
+    /// The required gains of the last `lookahead + 1` samples.
+    targets: [f32; MAX_LOOKAHEAD + 1],
+    target_pos: usize,
+    released: [f32; MAX_LOOKAHEAD],
+    release_state: f32,
+    /// Running sum of `released`, kept in double precision so it does not drift.
+    sum: f64,
+    gain: f32,
+}
+
+impl Default for Limiter {
+    fn default() -> Self {
+        Self {
+            lookahead: 1,
+            pos: 0,
+            delay: [[0.0; MAX_LOOKAHEAD]; MAX_CHANNELS],
+            history: [[0.0; 2]; MAX_CHANNELS],
+            targets: [1.0; MAX_LOOKAHEAD + 1],
+            target_pos: 0,
+            released: [1.0; MAX_LOOKAHEAD],
+            release_state: 1.0,
+            sum: 1.0,
+            gain: 1.0,
+        }
+    }
+}
+
+impl Limiter {
+    pub fn reset(&mut self) {
+        *self = Self {
+            lookahead: self.lookahead,
+            sum: self.lookahead as f64,
+            ..Self::default()
+        };
+    }
+
+    /// Sets the lookahead for the given sample rate, clearing the limiter's state.
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.lookahead =
+            (libm::roundf(LOOKAHEAD_MS / 1000.0 * sample_rate) as usize).clamp(1, MAX_LOOKAHEAD);
+        self.reset();
+    }
+
+    /// The delay the limiter adds to the signal, in samples.
+    pub fn latency(&self) -> usize {
+        self.lookahead
+    }
+
+    /// The gain applied to the sample frame that was processed last.
+    pub fn gain(&self) -> f32 {
+        self.gain
+    }
+
+    /// Limits a single sample frame in place. The frame is delayed by [`Self::latency()`].
+    pub fn process(&mut self, frame: &mut [f32], coefficients: &LimiterCoefficients) {
+        let peak = frame
+            .iter()
+            .zip(&mut self.history)
+            .fold(0.0f32, |peak, (sample, history)| {
+                let peak = peak.max(true_peak(*history, *sample));
+                *history = [history[1], *sample];
+
+                peak
+            });
+
+        // One step down, so rounding can't push the limited peak over the ceiling
+        let target = match peak > coefficients.ceiling {
+            true => (coefficients.ceiling / peak).next_down(),
+            false => 1.0,
+        };
+
+        // The average that reaches the delayed peak covers the samples after it, so the hold has
+        // to last until the peak itself comes out
+        self.targets[self.target_pos] = target;
+        self.target_pos = (self.target_pos + 1) % (self.lookahead + 1);
+        let held = self.targets[..=self.lookahead]
+            .iter()
+            .fold(1.0f32, |min, gain| min.min(*gain));
+
+        self.release_state = match held < self.release_state {
+            true => held,
+            false => self.release_state + (held - self.release_state) * coefficients.release,
+        };
+
+        self.sum += self.release_state as f64 - self.released[self.pos] as f64;
+        self.released[self.pos] = self.release_state;
+        self.gain = (self.sum / self.lookahead as f64).min(1.0) as f32;
+
+        for (sample, delay) in frame.iter_mut().zip(&mut self.delay) {
+            let delayed = delay[self.pos];
+            delay[self.pos] = *sample;
+
+            *sample = delayed * self.gain;
+        }
+
+        self.pos = (self.pos + 1) % self.lookahead;
+    }
+}
+
+/// Estimates the highest absolute value between the previous and the current sample using
+/// Catmull-Rom interpolation, with the next sample extrapolated linearly.
+fn true_peak([x0, x1]: [f32; 2], x2: f32) -> f32 {
+    let x3 = 2.0 * x2 - x1;
+
+    let a = -0.5 * x0 + 1.5 * x1 - 1.5 * x2 + 0.5 * x3;
+    let b = x0 - 2.5 * x1 + 2.0 * x2 - 0.5 * x3;
+    let c = -0.5 * x0 + 0.5 * x2;
+
+    (1..OVERSAMPLING)
+        .map(|i| i as f32 / OVERSAMPLING as f32)
+        .fold(x2.abs(), |peak, t| {
+            peak.max((((a * t + b) * t + c) * t + x1).abs())
+        })
+}
@@ -0,0 +1,59 @@
+use super::Choice;
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum BitOrder {
+    /// Start at the least significant mantissa bit.
+    #[default]
+    LowFirst,
+    /// Start at the sign bit.
+    HighFirst,
+    /// Jump around the word in a fixed pattern.
+    Scattered,
+}
+
+impl Choice for BitOrder {
+    const OPTIONS: &'static [Self] = &[Self::LowFirst, Self::HighFirst, Self::Scattered];
+}
+
+/// Distance between two consecutive bits in [`BitOrder::Scattered`]. Being odd, it visits every
+/// bit of a 32 or 64-bit word exactly once.
+const SCATTER_STEP: u32 = 23;
+
+/// The bit that gets switched at `step` when walking a `width` bits wide word in this order.
+fn bit_at(order: BitOrder, step: u32, width: u32) -> u32 {
+    match order {
+        BitOrder::LowFirst => step,
+        BitOrder::HighFirst => width - 1 - step,
+        BitOrder::Scattered => (step * SCATTER_STEP) % width,
+    }
+}
+
+/// Enables a share of the `width` bits of the word, starting at the end `order` picks.
+pub fn amount_mask(amount: f32, width: u32, order: BitOrder) -> u64 {
+    let count = libm::roundf(amount.clamp(0.0, 1.0) * width as f32) as u32;
+
+    (0..count).fold(0, |mask, step| mask | 1 << bit_at(order, step, width))
+}
+
+/// Travels from mask `a` at `0.0` to mask `b` at `1.0`, switching the differing bits in `order`.
+pub fn morph_mask(a: u64, b: u64, amount: f32, width: u32, order: BitOrder) -> u64 {
+    let changed = (a ^ b) & u64::MAX >> (64 - width);
+    let count = libm::roundf(amount.clamp(0.0, 1.0) * changed.count_ones() as f32) as u32;
+
+    let mut mask = a;
+    let mut switched = 0;
+
+    for step in 0..width {
+        if switched == count {
+            break;
+        }
+
+        let bit = 1 << bit_at(order, step, width);
+        if changed & bit != 0 {
+            mask ^= bit;
+            switched += 1;
+        }
+    }
+
+    mask
+}
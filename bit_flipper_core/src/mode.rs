@@ -0,0 +1,116 @@
+#[cfg(feature = "simd")]
+use core::simd::prelude::*;
+
+/// Amount of samples transformed at once by [`Mode::transform_slice()`].
+#[cfg(feature = "simd")]
+const LANES: usize = 8;
+/// Amount of samples transformed at once by [`Mode::transform_slice_f64()`].
+#[cfg(feature = "simd")]
+const WIDE_LANES: usize = 4;
+
+/// The bitwise operation that combines a sample with the mask.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum Mode {
+    And,
+    Or,
+    Not,
+    #[default]
+    Xor,
+}
+
+impl Mode {
+    pub fn transform(&self, sample: f32, mask: u32) -> f32 {
+        let bits = sample.to_bits();
+        let flipped = match self {
+            Self::And => bits & mask,
+            Self::Or => bits | mask,
+            Self::Not => !bits,
+            Self::Xor => bits ^ mask,
+        };
+
+        f32::from_bits(flipped).clamp(-1.0, 1.0)
+    }
+
+    /// Same as [`transform()`][Self::transform()], but for a whole slice of samples. Uses SIMD with
+    /// the `simd` feature.
+    #[cfg(feature = "simd")]
+    pub fn transform_slice(&self, samples: &mut [f32], mask: u32) {
+        let (head, body, tail) = samples.as_simd_mut::<LANES>();
+
+        for sample in head.iter_mut().chain(tail) {
+            *sample = self.transform(*sample, mask);
+        }
+
+        let mask = u32x8::splat(mask);
+        let (min, max) = (f32x8::splat(-1.0), f32x8::splat(1.0));
+
+        for chunk in body {
+            let bits = chunk.to_bits();
+            let flipped = match self {
+                Self::And => bits & mask,
+                Self::Or => bits | mask,
+                Self::Not => !bits,
+                Self::Xor => bits ^ mask,
+            };
+
+            *chunk = f32x8::from_bits(flipped).simd_clamp(min, max);
+        }
+    }
+
+    /// Same as [`transform()`][Self::transform()], but for a whole slice of samples. Uses SIMD with
+    /// the `simd` feature.
+    #[cfg(not(feature = "simd"))]
+    pub fn transform_slice(&self, samples: &mut [f32], mask: u32) {
+        for sample in samples {
+            *sample = self.transform(*sample, mask);
+        }
+    }
+
+    /// Converts the sample to `f64` before applying the 64-bit mask, then converts it back.
+    pub fn transform_f64(&self, sample: f32, mask: u64) -> f32 {
+        let bits = (sample as f64).to_bits();
+        let flipped = match self {
+            Self::And => bits & mask,
+            Self::Or => bits | mask,
+            Self::Not => !bits,
+            Self::Xor => bits ^ mask,
+        };
+
+        f64::from_bits(flipped).clamp(-1.0, 1.0) as f32
+    }
+
+    /// Same as [`transform_f64()`][Self::transform_f64()], but for a whole slice of samples. Uses
+    /// SIMD with the `simd` feature.
+    #[cfg(feature = "simd")]
+    pub fn transform_slice_f64(&self, samples: &mut [f32], mask: u64) {
+        let (head, body, tail) = samples.as_simd_mut::<WIDE_LANES>();
+
+        for sample in head.iter_mut().chain(tail) {
+            *sample = self.transform_f64(*sample, mask);
+        }
+
+        let mask = u64x4::splat(mask);
+        let (min, max) = (f64x4::splat(-1.0), f64x4::splat(1.0));
+
+        for chunk in body {
+            let bits = chunk.cast::<f64>().to_bits();
+            let flipped = match self {
+                Self::And => bits & mask,
+                Self::Or => bits | mask,
+                Self::Not => !bits,
+                Self::Xor => bits ^ mask,
+            };
+
+            *chunk = f64x4::from_bits(flipped).simd_clamp(min, max).cast();
+        }
+    }
+
+    /// Same as [`transform_f64()`][Self::transform_f64()], but for a whole slice of samples. Uses
+    /// SIMD with the `simd` feature.
+    #[cfg(not(feature = "simd"))]
+    pub fn transform_slice_f64(&self, samples: &mut [f32], mask: u64) {
+        for sample in samples {
+            *sample = self.transform_f64(*sample, mask);
+        }
+    }
+}
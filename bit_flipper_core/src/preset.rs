@@ -0,0 +1,215 @@
+//! The preset format, shared by the plugin and the command line tool. Needs the `preset` feature.
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "name": "Fizz",
+//!   "category": "Noise",
+//!   "mask": "0x000000000000003f",
+//!   "mode": "Xor",
+//!   "params": {
+//!     "pre_gain": 1.0,
+//!     "decimate_on": 0.0
+//!   }
+//! }
+//! ```
+//!
+//! - `category` groups presets in the editor's browser. Presets saved from the editor are put in
+//!   `User`, which is also where presets without a category end up.
+//! - `mask` holds all 64 mask bits as hex, bit `n` being `mask_bit_{n + 1}`.
+//! - `mode` is the name of one of the [`Mode`]s.
+//! - `params` maps the IDs of the remaining parameters to their plain values: gains are linear,
+//!   toggles are `0.0` or `1.0`, and choices are the index of the option in [`Choice::OPTIONS`].
+//!   Parameters that are missing get their value from [`defaults`], so presets keep working when
+//!   new parameters are added.
+//!
+//! The bypass and the recalled snapshot slot are never part of a preset, the slots themselves
+//! belong to the plugin's state rather than to a preset.
+
+use super::{
+    BitOrder, Choice, DecimateSettings, FREEZE_DIVISIONS, FreezeLength, GateAction, GateSettings,
+    GeneratorSettings, HistoryMode, HistorySettings, LimiterSettings, Mask, Mode, Settings,
+    TransitionStyle, Waveform, amount_mask, db_to_gain, defaults, morph_mask,
+};
+
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+/// Bumped whenever the format changes in a way older versions can't read.
+pub const PRESET_VERSION: u32 = 1;
+
+pub const USER_CATEGORY: &str = "User";
+
+#[derive(Debug)]
+pub enum PresetError {
+    Parse(serde_json::Error),
+    UnsupportedVersion(u32),
+    InvalidMask(String),
+    UnknownMode(String),
+}
+
+impl fmt::Display for PresetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(err) => write!(f, "Preset is not valid: {err}"),
+            Self::UnsupportedVersion(version) => {
+                write!(
+                    f,
+                    "Preset version {version} is newer than {PRESET_VERSION}."
+                )
+            }
+            Self::InvalidMask(mask) => write!(f, "Mask \"{mask}\" is not a 64-bit hex number."),
+            Self::UnknownMode(mode) => write!(f, "Mode \"{mode}\" does not exist."),
+        }
+    }
+}
+
+impl core::error::Error for PresetError {}
+
+impl From<serde_json::Error> for PresetError {
+    fn from(value: serde_json::Error) -> Self {
+        Self::Parse(value)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Preset {
+    pub version: u32,
+    pub name: String,
+    #[serde(default = "user_category")]
+    pub category: String,
+    pub mask: String,
+    pub mode: String,
+    #[serde(default)]
+    pub params: BTreeMap<String, f32>,
+}
+
+impl Preset {
+    pub fn from_json(json: &str) -> Result<Self, PresetError> {
+        let preset: Self = serde_json::from_str(json)?;
+
+        if preset.version > PRESET_VERSION {
+            return Err(PresetError::UnsupportedVersion(preset.version));
+        }
+
+        Ok(preset)
+    }
+
+    pub fn to_json(&self) -> Result<String, PresetError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// The mask bits, before the macros are applied.
+    pub fn mask(&self) -> Result<Mask, PresetError> {
+        let hex = self.mask.trim_start_matches("0x");
+
+        u64::from_str_radix(hex, 16).map_err(|_| PresetError::InvalidMask(self.mask.clone()))
+    }
+
+    pub fn mode(&self) -> Result<Mode, PresetError> {
+        match self.mode.to_lowercase().as_str() {
+            "and" => Ok(Mode::And),
+            "or" => Ok(Mode::Or),
+            "not" => Ok(Mode::Not),
+            "xor" => Ok(Mode::Xor),
+            _ => Err(PresetError::UnknownMode(self.mode.clone())),
+        }
+    }
+
+    /// The plain value of a parameter, or `default` when the preset doesn't have it.
+    pub fn value(&self, id: &str, default: f32) -> f32 {
+        self.params.get(id).copied().unwrap_or(default)
+    }
+
+    fn toggle(&self, id: &str, default: bool) -> bool {
+        self.params.get(id).map_or(default, |value| *value >= 0.5)
+    }
+
+    /// The option a choice parameter is set to. Out of range indices get the default.
+    fn choice<T: Choice>(&self, id: &str) -> T {
+        self.params
+            .get(id)
+            .map_or_else(T::default, |index| T::from_index(*index as usize))
+    }
+
+    /// Settings for the processing chain. Mask B isn't part of a preset, so the morph macro
+    /// morphs towards an empty mask, like it does in a new instance of the plugin.
+    pub fn settings(&self) -> Result<Settings, PresetError> {
+        let double_precision = self.toggle("double_precision", false);
+
+        let (mask, width) = match double_precision {
+            true => (self.mask()?, 64),
+            false => (self.mask()? as u32 as u64, 32),
+        };
+
+        let morph_order: BitOrder = self.choice("macro_morph_order");
+        let amount_order: BitOrder = self.choice("macro_amount_order");
+        let morphed = morph_mask(mask, 0, self.value("macro_morph", 0.0), width, morph_order);
+        let mask = morphed | amount_mask(self.value("macro_amount", 0.0), width, amount_order);
+
+        let freeze_division = self.value("freeze_division", defaults::FREEZE_DIVISION as f32);
+        let freeze_beats = FREEZE_DIVISIONS
+            .get(freeze_division as usize)
+            .copied()
+            .unwrap_or(FREEZE_DIVISIONS[defaults::FREEZE_DIVISION]);
+
+        Ok(Settings {
+            generator: self
+                .toggle("generator_on", false)
+                .then(|| GeneratorSettings {
+                    waveform: self.choice::<Waveform>("generator_waveform"),
+                    frequency: self.value("generator_frequency", defaults::GENERATOR_FREQUENCY),
+                    level: self.value("generator_level", db_to_gain(defaults::GENERATOR_LEVEL_DB)),
+                    replace: self.toggle("generator_replace", defaults::GENERATOR_REPLACE),
+                }),
+            mask,
+            mode: self.mode()?,
+            double_precision,
+            spread: self.toggle("channel_spread", false),
+            lfe_channel: None,
+            pre_gain: self.value("pre_gain", defaults::PRE_GAIN),
+            decimate: self.toggle("decimate_on", false).then(|| DecimateSettings {
+                rate: self.value("decimate_rate", defaults::DECIMATE_RATE),
+                filter: self.toggle("decimate_filter", defaults::DECIMATE_FILTER),
+            }),
+            truncate: self
+                .toggle("truncate_on", false)
+                .then(|| self.value("truncate_depth", defaults::TRUNCATE_DEPTH as f32) as u32),
+            freeze: self.toggle("freeze_on", false).then(|| {
+                match self.toggle("freeze_sync", false) {
+                    true => FreezeLength::Beats(freeze_beats),
+                    false => FreezeLength::Milliseconds(
+                        self.value("freeze_time", defaults::FREEZE_TIME_MS),
+                    ),
+                }
+            }),
+            tempo: None,
+            transition: self.choice::<TransitionStyle>("transition_style"),
+            transition_ms: self.value("transition_time", defaults::TRANSITION_TIME_MS),
+            history: self.toggle("history_on", false).then(|| HistorySettings {
+                mode: self.choice::<HistoryMode>("history_mode"),
+                delay: self.value("history_delay", defaults::HISTORY_DELAY as f32) as usize,
+                reset: self.value("history_reset", defaults::HISTORY_RESET as f32) as usize,
+            }),
+            gate: self.toggle("gate_on", false).then(|| GateSettings {
+                threshold_db: self.value("gate_threshold", defaults::GATE_THRESHOLD_DB),
+                hysteresis_db: self.value("gate_hysteresis", defaults::GATE_HYSTERESIS_DB),
+                attack_ms: self.value("gate_attack", defaults::GATE_ATTACK_MS),
+                release_ms: self.value("gate_release", defaults::GATE_RELEASE_MS),
+                action: self.choice::<GateAction>("gate_action"),
+                sidechain: false,
+            }),
+            limiter: self.toggle("limiter_on", false).then(|| LimiterSettings {
+                ceiling_db: self.value("limiter_ceiling", defaults::LIMITER_CEILING_DB),
+                release_ms: self.value("limiter_release", defaults::LIMITER_RELEASE_MS),
+            }),
+            bypass: false,
+        })
+    }
+}
+
+fn user_category() -> String {
+    USER_CATEGORY.to_string()
+}
@@ -0,0 +1,459 @@
+use super::{
+    BYPASS_FADE_MS, Bypass, Decimator, Freeze, FreezeLength, Gate, GateAction, GateCoefficients,
+    GateSettings, Generator, GeneratorSettings, History, HistorySettings, Limiter,
+    LimiterCoefficients, LimiterSettings, MAX_CHANNELS, Mask, MaskTransition, Mode, Transform,
+    TransitionStyle, defaults, smoothing_coefficient, truncate_slice,
+};
+
+use core::ops::Range;
+
+/// Upper bound for the amount of samples processed in one go. Longer buffers are split up.
+pub const MAX_BLOCK_SIZE: usize = 128;
+
+/// How quickly the pre-gain follows changes to its setting.
+const PRE_GAIN_SMOOTHING_MS: f32 = 5.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DecimateSettings {
+    pub rate: f32,
+    pub filter: bool,
+}
+
+/// A snapshot of everything the processing chain depends on. It is taken once per block so the
+/// parameters don't have to be read for every sample.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Settings {
+    /// Test signal fed into the chain in place of or on top of the input.
+    pub generator: Option<GeneratorSettings>,
+
+    pub mask: Mask,
+    pub mode: Mode,
+    /// Process the sample as an `f64`, which gives the mask 64 bits to work with.
+    pub double_precision: bool,
+    /// Rotate the mantissa mask per channel instead of using the same mask everywhere.
+    pub spread: bool,
+    /// The LFE channel of a surround layout, which the spread leaves alone.
+    pub lfe_channel: Option<usize>,
+    /// Linear gain applied to the input before any of the bit stages.
+    pub pre_gain: f32,
+
+    pub decimate: Option<DecimateSettings>,
+    /// Amount of mantissa bits kept by the truncation stage.
+    pub truncate: Option<u32>,
+    /// Holds the masked bits of a captured sample for this long, in place of the transformed ones.
+    pub freeze: Option<FreezeLength>,
+    /// The host tempo in beats per minute, if it reports one.
+    pub tempo: Option<f32>,
+
+    pub transition: TransitionStyle,
+    pub transition_ms: f32,
+
+    /// Takes the mask from earlier samples. Mask transitions don't apply while this is enabled.
+    pub history: Option<HistorySettings>,
+
+    pub gate: Option<GateSettings>,
+    pub limiter: Option<LimiterSettings>,
+
+    /// Fades over to the unprocessed input.
+    pub bypass: bool,
+}
+
+impl Settings {
+    pub fn transform(&self) -> Transform {
+        Transform {
+            mask: self.mask,
+            mode: self.mode,
+            double_precision: self.double_precision,
+            spread: self.spread,
+            lfe_channel: self.lfe_channel,
+        }
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            generator: None,
+            mask: 0,
+            mode: Mode::default(),
+            double_precision: false,
+            spread: false,
+            lfe_channel: None,
+            pre_gain: defaults::PRE_GAIN,
+            decimate: None,
+            truncate: None,
+            freeze: None,
+            tempo: None,
+            transition: TransitionStyle::default(),
+            transition_ms: defaults::TRANSITION_TIME_MS,
+            history: None,
+            gate: None,
+            limiter: None,
+            bypass: false,
+        }
+    }
+}
+
+/// The complete processing chain:
+/// generator -> pre-gain -> decimation -> truncation -> mask transform or history -> freeze ->
+/// gate -> limiter -> bypass. The freeze holds bits from before the transform.
+#[derive(Debug, Clone)]
+pub struct Processor {
+    sample_rate: f32,
+
+    generator: Generator,
+    decimators: [Decimator; MAX_CHANNELS],
+    freezes: [Freeze; MAX_CHANNELS],
+    transition: MaskTransition,
+    history: [History; MAX_CHANNELS],
+    gate: Gate,
+    limiter: Limiter,
+    limiter_active: bool,
+    bypass: Bypass,
+
+    /// The block as it came in, for the bypass.
+    input: [[f32; MAX_BLOCK_SIZE]; MAX_CHANNELS],
+    /// The signal right before the bit stages, which a closed gate lets through.
+    dry: [[f32; MAX_BLOCK_SIZE]; MAX_CHANNELS],
+    /// The signal going into the mask transform, which the freeze captures from.
+    untransformed: [[f32; MAX_BLOCK_SIZE]; MAX_CHANNELS],
+    gate_gains: [f32; MAX_BLOCK_SIZE],
+    /// Follows the pre-gain setting smoothly, so modulating it doesn't zipper. Unset after a reset,
+    /// when it jumps straight to the setting.
+    pre_gain: Option<f32>,
+    pre_gains: [f32; MAX_BLOCK_SIZE],
+    limiter_gain: f32,
+}
+
+impl Default for Processor {
+    fn default() -> Self {
+        Self::new(44100.0)
+    }
+}
+
+impl Processor {
+    pub fn new(sample_rate: f32) -> Self {
+        let mut processor = Self {
+            sample_rate,
+
+            generator: Default::default(),
+            decimators: Default::default(),
+            freezes: Default::default(),
+            transition: Default::default(),
+            history: Default::default(),
+            gate: Default::default(),
+            limiter: Default::default(),
+            limiter_active: false,
+            bypass: Default::default(),
+
+            input: [[0.0; MAX_BLOCK_SIZE]; MAX_CHANNELS],
+            dry: [[0.0; MAX_BLOCK_SIZE]; MAX_CHANNELS],
+            untransformed: [[0.0; MAX_BLOCK_SIZE]; MAX_CHANNELS],
+            gate_gains: [0.0; MAX_BLOCK_SIZE],
+            pre_gain: None,
+            pre_gains: [1.0; MAX_BLOCK_SIZE],
+            limiter_gain: 1.0,
+        };
+
+        processor.set_sample_rate(sample_rate);
+        processor
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate;
+        self.limiter.set_sample_rate(sample_rate);
+    }
+
+    pub fn reset(&mut self) {
+        self.generator.reset();
+        self.decimators.iter_mut().for_each(Decimator::reset);
+        self.freezes.iter_mut().for_each(Freeze::reset);
+        self.transition.reset();
+        self.history.iter_mut().for_each(History::reset);
+        self.gate.reset();
+        self.limiter.reset();
+        self.bypass.reset();
+        self.pre_gain = None;
+        self.limiter_gain = 1.0;
+    }
+
+    /// Starts over at `position` on the timeline, in samples. Every stage that depends on what came
+    /// before is cleared and the noise gets reseeded, so renders from the same position match.
+    /// Freeze periods fall on multiples of their length counted from the start of the timeline,
+    /// which puts synced ones on the beat grid as long as the tempo doesn't change.
+    /// The bypass fade and the pre-gain smoothing only follow the settings, so they carry on.
+    pub fn restart(&mut self, position: i64) {
+        self.generator.restart(position);
+        self.decimators.iter_mut().for_each(Decimator::reset);
+        self.freezes
+            .iter_mut()
+            .for_each(|freeze| freeze.restart(position));
+        self.transition.reset();
+        self.history.iter_mut().for_each(History::reset);
+        self.gate.reset();
+        self.limiter.reset();
+    }
+
+    /// The latency the chain adds with these settings, in samples.
+    pub fn latency(&self, settings: &Settings) -> usize {
+        match settings.limiter {
+            Some(_) => self.limiter.latency(),
+            None => 0,
+        }
+    }
+
+    /// Whether the gate was enabled and closed at the end of the last processed block.
+    pub fn gate_closed(&self, settings: &Settings) -> bool {
+        settings.gate.is_some() && !self.gate.is_open()
+    }
+
+    /// The lowest gain the limiter applied during the last call to [`process()`][Self::process()].
+    pub fn limiter_gain(&self) -> f32 {
+        self.limiter_gain
+    }
+
+    /// Processes the channels in place. All channels need to be the same length, and the
+    /// sidechain, if any, needs to be at least that long as well.
+    pub fn process(
+        &mut self,
+        channels: &mut [&mut [f32]],
+        sidechain: Option<&[&mut [f32]]>,
+        settings: &Settings,
+    ) {
+        let len = channels.first().map_or(0, |samples| samples.len());
+
+        if settings.limiter.is_some() != self.limiter_active {
+            self.limiter_active = settings.limiter.is_some();
+            self.limiter.reset();
+        }
+
+        let gate = settings
+            .gate
+            .map(|gate| (gate, GateCoefficients::new(&gate, self.sample_rate)));
+        let limiter = settings
+            .limiter
+            .map(|limiter| LimiterCoefficients::new(&limiter, self.sample_rate));
+
+        self.limiter_gain = 1.0;
+
+        let latency = self.latency(settings);
+        let bypass_step = 1.0 / (BYPASS_FADE_MS / 1000.0 * self.sample_rate);
+
+        for start in (0..len).step_by(MAX_BLOCK_SIZE) {
+            let range = start..(start + MAX_BLOCK_SIZE).min(len);
+
+            for (samples, input) in channels.iter().zip(&mut self.input) {
+                input[..range.len()].copy_from_slice(&samples[range.clone()]);
+            }
+
+            if let Some(generator) = &settings.generator {
+                self.generate(channels, range.clone(), generator);
+            }
+
+            if let Some((gate, coefficients)) = &gate {
+                let key = match gate.sidechain {
+                    true => sidechain.unwrap_or(channels),
+                    false => channels,
+                };
+
+                self.compute_gate(key, range.clone(), coefficients);
+            }
+
+            self.process_block(channels, range.clone(), settings);
+
+            if let Some(coefficients) = &limiter {
+                self.apply_limiter(channels, range.clone(), coefficients);
+            }
+
+            self.bypass.process(
+                channels,
+                &self.input,
+                range,
+                latency,
+                settings.bypass,
+                bypass_step,
+            );
+        }
+    }
+
+    fn generate(
+        &mut self,
+        channels: &mut [&mut [f32]],
+        range: Range<usize>,
+        settings: &GeneratorSettings,
+    ) {
+        for frame in range {
+            let value = self.generator.next(settings, self.sample_rate);
+
+            for samples in channels.iter_mut() {
+                samples[frame] = match settings.replace {
+                    true => value,
+                    false => samples[frame] + value,
+                };
+            }
+        }
+    }
+
+    fn compute_gate(
+        &mut self,
+        key: &[&mut [f32]],
+        range: Range<usize>,
+        coefficients: &GateCoefficients,
+    ) {
+        for (gain, frame) in self.gate_gains.iter_mut().zip(range) {
+            let level = key
+                .iter()
+                .fold(0.0f32, |peak, samples| peak.max(samples[frame].abs()));
+
+            *gain = self.gate.process(level, coefficients);
+        }
+    }
+
+    fn compute_pre_gain(&mut self, len: usize, target: f32) {
+        let coefficient = smoothing_coefficient(PRE_GAIN_SMOOTHING_MS, self.sample_rate);
+        let mut gain = self.pre_gain.unwrap_or(target);
+
+        for pre_gain in &mut self.pre_gains[..len] {
+            gain += (target - gain) * coefficient;
+            *pre_gain = gain;
+        }
+
+        self.pre_gain = Some(gain);
+    }
+
+    fn process_block(
+        &mut self,
+        channels: &mut [&mut [f32]],
+        range: Range<usize>,
+        settings: &Settings,
+    ) {
+        let len = range.len();
+        let transform = settings.transform();
+        let freeze_length = settings
+            .freeze
+            .map(|freeze| freeze.samples(self.sample_rate, settings.tempo));
+
+        self.compute_pre_gain(len, settings.pre_gain);
+
+        for (((samples, decimator), dry), untransformed) in channels
+            .iter_mut()
+            .zip(&mut self.decimators)
+            .zip(&mut self.dry)
+            .zip(&mut self.untransformed)
+        {
+            let samples = &mut samples[range.clone()];
+
+            for (sample, gain) in samples.iter_mut().zip(&self.pre_gains) {
+                *sample *= gain;
+            }
+
+            if settings.gate.is_some() {
+                dry[..len].copy_from_slice(samples);
+            }
+
+            if let Some(decimate) = settings.decimate {
+                for sample in samples.iter_mut() {
+                    *sample = decimator.process(*sample, decimate.rate, decimate.filter);
+                }
+            }
+
+            if let Some(depth) = settings.truncate {
+                truncate_slice(samples, depth);
+            }
+
+            if freeze_length.is_some() {
+                untransformed[..len].copy_from_slice(samples);
+            }
+        }
+
+        match settings.history {
+            Some(history) => self.apply_history(channels, range.clone(), settings, &history),
+            None => {
+                let length = settings.transition_ms / 1000.0 * self.sample_rate;
+                self.transition.process(
+                    channels,
+                    range.clone(),
+                    settings.transform(),
+                    settings.transition,
+                    length,
+                );
+            }
+        }
+
+        if let Some(length) = freeze_length {
+            for (index, ((samples, freeze), untransformed)) in channels
+                .iter_mut()
+                .zip(&mut self.freezes)
+                .zip(&self.untransformed)
+                .enumerate()
+            {
+                let transform = transform.for_channel(index);
+
+                for (sample, input) in samples[range.clone()].iter_mut().zip(untransformed) {
+                    let (input, output) = (transform.bits(*input), transform.bits(*sample));
+                    let bits = freeze.process(input, output, transform.mask, length);
+
+                    *sample = transform.from_bits(bits);
+                }
+            }
+        }
+
+        if let Some(gate) = settings.gate {
+            let gains = &self.gate_gains[..len];
+
+            for (samples, dry) in channels.iter_mut().zip(&self.dry) {
+                let samples = &mut samples[range.clone()];
+
+                for ((sample, dry), gain) in samples.iter_mut().zip(&dry[..len]).zip(gains) {
+                    *sample = match gate.action {
+                        GateAction::Bypass => dry + (*sample - dry) * gain,
+                        GateAction::Mute => *sample * gain,
+                    };
+                }
+            }
+        }
+    }
+
+    fn apply_history(
+        &mut self,
+        channels: &mut [&mut [f32]],
+        range: Range<usize>,
+        settings: &Settings,
+        history_settings: &HistorySettings,
+    ) {
+        let transform = settings.transform();
+
+        for (index, (samples, history)) in channels.iter_mut().zip(&mut self.history).enumerate() {
+            let transform = transform.for_channel(index);
+
+            for sample in &mut samples[range.clone()] {
+                let mask = history.process(transform.bits(*sample), history_settings);
+
+                *sample = transform.with_mask(mask & transform.mask).apply(*sample);
+            }
+        }
+    }
+
+    fn apply_limiter(
+        &mut self,
+        channels: &mut [&mut [f32]],
+        range: Range<usize>,
+        coefficients: &LimiterCoefficients,
+    ) {
+        let mut frame = [0.0; MAX_CHANNELS];
+        let frame = &mut frame[..channels.len().min(MAX_CHANNELS)];
+
+        for index in range {
+            for (value, samples) in frame.iter_mut().zip(channels.iter()) {
+                *value = samples[index];
+            }
+
+            self.limiter.process(frame, coefficients);
+            self.limiter_gain = self.limiter_gain.min(self.limiter.gain());
+
+            for (value, samples) in frame.iter().zip(channels.iter_mut()) {
+                samples[index] = *value;
+            }
+        }
+    }
+}
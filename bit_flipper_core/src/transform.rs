@@ -0,0 +1,75 @@
+use super::{Mask, Mode};
+
+/// How many bits the mantissa part of the mask rotates by for every further channel when the
+/// masks are spread.
+const SPREAD_STEP: u32 = 5;
+
+/// Everything that decides how a sample gets flipped.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Transform {
+    pub mask: Mask,
+    pub mode: Mode,
+    /// Flip the bits of the sample converted to `f64` instead of the `f32` itself.
+    pub double_precision: bool,
+    /// Decorrelate the channels by giving each one a differently rotated mantissa mask.
+    pub spread: bool,
+    /// The channel carrying the low frequency effects, which keeps the mask as is when spread.
+    pub lfe_channel: Option<usize>,
+}
+
+impl Transform {
+    pub fn apply(&self, sample: f32) -> f32 {
+        match self.double_precision {
+            true => self.mode.transform_f64(sample, self.mask),
+            false => self.mode.transform(sample, self.mask as u32),
+        }
+    }
+
+    pub fn apply_slice(&self, samples: &mut [f32]) {
+        match self.double_precision {
+            true => self.mode.transform_slice_f64(samples, self.mask),
+            false => self.mode.transform_slice(samples, self.mask as u32),
+        }
+    }
+
+    /// The bits of `sample` in this transform's precision, lined up with the mask.
+    pub fn bits(&self, sample: f32) -> u64 {
+        match self.double_precision {
+            true => (sample as f64).to_bits(),
+            false => sample.to_bits() as u64,
+        }
+    }
+
+    /// Turns [`bits()`][Self::bits()] back into a sample.
+    pub fn from_bits(&self, bits: u64) -> f32 {
+        match self.double_precision {
+            true => f64::from_bits(bits) as f32,
+            false => f32::from_bits(bits as u32),
+        }
+    }
+
+    /// The transform for the channel at `index`. Only differs between channels when spread, and
+    /// never for the first channel or the LFE channel.
+    pub fn for_channel(self, index: usize) -> Self {
+        if !self.spread || index == 0 || self.lfe_channel == Some(index) {
+            return self;
+        }
+
+        let mantissa_bits = match self.double_precision {
+            true => 52,
+            false => 23,
+        };
+        let field = (1u64 << mantissa_bits) - 1;
+        let shift = (index as u32 * SPREAD_STEP) % mantissa_bits;
+
+        let mantissa = self.mask & field;
+        let rotated = ((mantissa << shift) | (mantissa >> (mantissa_bits - shift))) & field;
+
+        self.with_mask(self.mask & !field | rotated)
+    }
+
+    /// The same transform with a different mask.
+    pub fn with_mask(self, mask: Mask) -> Self {
+        Self { mask, ..self }
+    }
+}
@@ -0,0 +1,242 @@
+use super::{Choice, Transform};
+
+use core::f32::consts::FRAC_PI_2;
+use core::ops::Range;
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum TransitionStyle {
+    /// Switch to the new mask instantly.
+    Off,
+    /// Linear crossfade between the old and the new transform result. Keeps the level when both
+    /// results are close, like when only a few low bits change.
+    Crossfade,
+    /// Switch the changed bits over one at a time, starting from the least significant one.
+    BitMorph,
+    /// Equal-power crossfade, keeps the loudness when the results differ a lot.
+    #[default]
+    EqualPower,
+}
+
+impl TransitionStyle {
+    /// The gains of the results being faded out and in, `progress` into a crossfade.
+    fn gains(self, progress: f32) -> (f32, f32) {
+        match self {
+            Self::EqualPower => {
+                let angle = progress * FRAC_PI_2;
+
+                (libm::cosf(angle), libm::sinf(angle))
+            }
+            _ => (1.0 - progress, progress),
+        }
+    }
+
+    /// The level of a mix with these gains, which the gains of a crossfade add up to.
+    fn level(self, gains: impl Iterator<Item = f32>) -> f32 {
+        match self {
+            Self::EqualPower => libm::sqrtf(gains.map(|gain| gain * gain).sum()),
+            _ => gains.sum(),
+        }
+    }
+}
+
+impl Choice for TransitionStyle {
+    const OPTIONS: &'static [Self] =
+        &[Self::Off, Self::Crossfade, Self::BitMorph, Self::EqualPower];
+}
+
+/// How many results a crossfade can fade out at once. Interrupting a crossfade keeps fading out
+/// everything that was audible, so each interruption can add one.
+const MAX_SOURCES: usize = 4;
+
+/// Transforms being faded out, with the gain each had when the transition started. Unused
+/// entries have a gain of `0.0`.
+type Sources = [(Transform, f32); MAX_SOURCES];
+
+fn single_source(transform: Transform) -> Sources {
+    let mut sources = [(Transform::default(), 0.0); MAX_SOURCES];
+    sources[0] = (transform, 1.0);
+
+    sources
+}
+
+/// Glides between mask and mode settings so automation does not produce clicks.
+#[derive(Debug, Clone, Copy)]
+pub struct MaskTransition {
+    from: Sources,
+    to: Transform,
+    style: TransitionStyle,
+    /// Runs from `0.0` to `1.0` over the course of a transition.
+    progress: f32,
+    step: f32,
+    /// Mask used while bit-morphing, with part of the changed bits already switched over.
+    morph_mask: u64,
+    initialized: bool,
+}
+
+impl Default for MaskTransition {
+    fn default() -> Self {
+        Self {
+            from: single_source(Transform::default()),
+            to: Transform::default(),
+            style: TransitionStyle::Off,
+            progress: 1.0,
+            step: 0.0,
+            morph_mask: 0,
+            initialized: false,
+        }
+    }
+}
+
+impl MaskTransition {
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Transforms `range` of every channel, picking up setting changes at the start of the block.
+    /// `length` is the transition time in samples.
+    pub fn process(
+        &mut self,
+        channels: &mut [&mut [f32]],
+        range: Range<usize>,
+        transform: Transform,
+        style: TransitionStyle,
+        length: f32,
+    ) {
+        self.retarget(transform, style, length);
+
+        if !self.is_running() {
+            for (index, samples) in channels.iter_mut().enumerate() {
+                transform
+                    .for_channel(index)
+                    .apply_slice(&mut samples[range.clone()]);
+            }
+
+            return;
+        }
+
+        for frame in range {
+            self.advance();
+
+            for (index, samples) in channels.iter_mut().enumerate() {
+                samples[frame] = self.apply(samples[frame], index);
+            }
+        }
+    }
+
+    fn retarget(&mut self, target: Transform, style: TransitionStyle, length: f32) {
+        if !self.initialized || style == TransitionStyle::Off {
+            self.from = single_source(target);
+            self.to = target;
+            self.progress = 1.0;
+            self.initialized = true;
+        } else if target != self.to {
+            // The new transition starts from whatever is audible right now
+            self.from = match self.is_running() {
+                true if self.is_morphing() => single_source(self.to.with_mask(self.morph_mask)),
+                true => self.current_mix(),
+                false => single_source(self.to),
+            };
+
+            self.to = target;
+            self.style = style;
+            self.progress = 0.0;
+            self.step = 1.0 / length.max(1.0);
+        }
+    }
+
+    /// The gains every transform has at this point of the crossfade, merging duplicates. When
+    /// there are too many, the quietest one is dropped and the others make up for it.
+    fn current_mix(&self) -> Sources {
+        let (fade_out, fade_in) = self.style.gains(self.progress);
+        let gains = self
+            .from
+            .iter()
+            .map(|(transform, gain)| (*transform, gain * fade_out))
+            .chain([(self.to, fade_in)]);
+
+        let mut mix = [(Transform::default(), 0.0); MAX_SOURCES];
+
+        for (transform, gain) in gains.filter(|(_, gain)| *gain > 0.0) {
+            if let Some(source) = mix.iter_mut().find(|(t, g)| *g > 0.0 && *t == transform) {
+                source.1 += gain;
+            } else if let Some(source) = mix.iter_mut().find(|(_, g)| *g == 0.0) {
+                *source = (transform, gain);
+            } else if let Some(source) = mix.iter_mut().min_by(|a, b| a.1.total_cmp(&b.1))
+                && source.1 < gain
+            {
+                *source = (transform, gain);
+            }
+        }
+
+        let level = self.style.level(mix.iter().map(|(_, gain)| *gain));
+        for (_, gain) in &mut mix {
+            *gain /= level;
+        }
+
+        mix
+    }
+
+    /// Whether the running transition switches bits over instead of crossfading. Mode and
+    /// precision changes have no bits to morph, and neither does a mix of several results, so
+    /// those always get crossfaded.
+    fn is_morphing(&self) -> bool {
+        let [(from, _), rest @ ..] = self.from;
+
+        self.style == TransitionStyle::BitMorph
+            && rest.iter().all(|(_, gain)| *gain == 0.0)
+            && from.with_mask(self.to.mask) == self.to
+    }
+
+    fn advance(&mut self) {
+        if !self.is_running() {
+            return;
+        }
+
+        self.progress = (self.progress + self.step).min(1.0);
+        self.morph_mask = morph_mask(self.from[0].0.mask, self.to.mask, self.progress);
+    }
+
+    fn apply(&self, sample: f32, channel: usize) -> f32 {
+        let to = self.to.for_channel(channel);
+
+        if !self.is_running() {
+            return to.apply(sample);
+        }
+
+        if self.is_morphing() {
+            let morphed = self.to.with_mask(self.morph_mask);
+
+            return morphed.for_channel(channel).apply(sample);
+        }
+
+        let (fade_out, fade_in) = self.style.gains(self.progress);
+
+        self.from
+            .iter()
+            .filter(|(_, gain)| *gain > 0.0)
+            .fold(to.apply(sample) * fade_in, |mix, (from, gain)| {
+                mix + from.for_channel(channel).apply(sample) * gain * fade_out
+            })
+    }
+
+    fn is_running(&self) -> bool {
+        self.progress < 1.0
+    }
+}
+
+/// Switches the bits that differ between `from` and `to` one by one, least significant first,
+/// spreading them evenly over the transition.
+fn morph_mask(from: u64, to: u64, progress: f32) -> u64 {
+    let mut changed = from ^ to;
+    let switched = (progress * (changed.count_ones() + 1) as f32) as u32;
+
+    let mut mask = from;
+    for _ in 0..switched.min(changed.count_ones()) {
+        let lowest = changed & changed.wrapping_neg();
+
+        mask ^= lowest;
+        changed ^= lowest;
+    }
+
+    mask
+}
@@ -0,0 +1,15 @@
+/// Number of explicitly stored mantissa bits in an `f32`.
+pub const MANTISSA_BITS: u32 = 23;
+
+/// Zeroes out the lowest mantissa bits so only the top `depth` bits remain.
+pub fn truncate_slice(samples: &mut [f32], depth: u32) {
+    let mask = truncation_mask(depth);
+
+    for sample in samples {
+        *sample = f32::from_bits(sample.to_bits() & mask);
+    }
+}
+
+fn truncation_mask(depth: u32) -> u32 {
+    u32::MAX << (MANTISSA_BITS - depth.min(MANTISSA_BITS))
+}
@@ -0,0 +1,70 @@
+//! Freeze holds the masked bits of the input in place of the transformed ones, in periods that
+//! start on the timeline.
+
+use bit_flipper_core::{FreezeLength, Mode, Processor, Settings, TransitionStyle};
+
+const SAMPLE_RATE: f32 = 48000.0;
+const LENGTH: usize = 256;
+const MASK: u64 = 0xff;
+
+fn settings(mode: Mode) -> Settings {
+    Settings {
+        mask: MASK,
+        mode,
+        // 6000 samples, without any rounding
+        freeze: Some(FreezeLength::Beats(0.25)),
+        tempo: Some(120.0),
+        transition: TransitionStyle::Off,
+        ..Settings::default()
+    }
+}
+
+/// A ramp that changes the lowest mantissa bits on every sample.
+fn input() -> Vec<f32> {
+    (0..LENGTH).map(|i| 0.25 + i as f32 * 1e-5).collect()
+}
+
+fn process(processor: &mut Processor, settings: &Settings) -> Vec<f32> {
+    let mut samples = input();
+    processor.process(&mut [samples.as_mut_slice()], None, settings);
+
+    samples
+}
+
+#[test]
+fn frozen_bits_skip_the_transform() {
+    for mode in [Mode::Xor, Mode::Or, Mode::And] {
+        let mut processor = Processor::new(SAMPLE_RATE);
+        let output = process(&mut processor, &settings(mode));
+
+        let held = input()[0].to_bits() as u64 & MASK;
+
+        for (sample, input) in output.iter().zip(input()) {
+            let bits = sample.to_bits() as u64;
+            let transformed = mode.transform(input, MASK as u32).to_bits() as u64;
+
+            assert_eq!(bits & MASK, held, "{mode:?}");
+            assert_eq!(bits & !MASK, transformed & !MASK, "{mode:?}");
+        }
+    }
+}
+
+#[test]
+fn freeze_periods_start_on_the_timeline() {
+    let length = FreezeLength::Beats(0.25).samples(SAMPLE_RATE, Some(120.0)) as i64;
+    // Just before the second period
+    let position = 5900;
+
+    let mut processor = Processor::new(SAMPLE_RATE);
+    processor.restart(position);
+    let output = process(&mut processor, &settings(Mode::Xor));
+
+    let captures: Vec<_> = output
+        .windows(2)
+        .enumerate()
+        .filter(|(_, pair)| (pair[0].to_bits() ^ pair[1].to_bits()) as u64 & MASK != 0)
+        .map(|(index, _)| position + index as i64 + 1)
+        .collect();
+
+    assert_eq!(captures, [length]);
+}
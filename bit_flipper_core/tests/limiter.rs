@@ -0,0 +1,48 @@
+//! Whatever comes in, the limited signal stays below the ceiling.
+
+use bit_flipper_core::{Limiter, LimiterCoefficients, LimiterSettings, db_to_gain};
+
+const SAMPLE_RATE: f32 = 48000.0;
+const LENGTH: usize = 48000;
+
+/// Noise with loud single-sample spikes, the hardest case for the lookahead.
+fn input() -> Vec<[f32; 2]> {
+    let mut state = 0x1234_5678u32;
+    let mut next = move || {
+        state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+        (state >> 8) as f32 / (1 << 24) as f32 * 2.0 - 1.0
+    };
+
+    (0..LENGTH)
+        .map(|n| {
+            let level = if n % 97 == 0 { 8.0 } else { 0.5 };
+            [next() * level, next() * level]
+        })
+        .collect()
+}
+
+#[test]
+fn output_never_exceeds_the_ceiling() {
+    for (ceiling_db, release_ms) in [(-1.0, 100.0), (-6.0, 1.0), (-3.0, 0.1), (0.0, 0.01)] {
+        let settings = LimiterSettings {
+            ceiling_db,
+            release_ms,
+        };
+        let coefficients = LimiterCoefficients::new(&settings, SAMPLE_RATE);
+        let ceiling = db_to_gain(ceiling_db);
+
+        let mut limiter = Limiter::default();
+        limiter.set_sample_rate(SAMPLE_RATE);
+
+        for (n, mut frame) in input().into_iter().enumerate() {
+            limiter.process(&mut frame, &coefficients);
+
+            for sample in frame {
+                assert!(
+                    sample.abs() <= ceiling,
+                    "{sample} exceeds {ceiling} at sample {n} ({ceiling_db} dB, {release_ms} ms)"
+                );
+            }
+        }
+    }
+}
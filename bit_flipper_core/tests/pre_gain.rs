@@ -0,0 +1,42 @@
+//! The pre-gain follows changes smoothly, so modulating it doesn't zipper.
+
+use bit_flipper_core::{Processor, Settings};
+
+const SAMPLE_RATE: f32 = 48000.0;
+/// 10 ms, twice the smoothing time.
+const LENGTH: usize = 480;
+
+fn process(processor: &mut Processor, pre_gain: f32) -> Vec<f32> {
+    let mut samples = vec![0.5; LENGTH];
+    let settings = Settings {
+        pre_gain,
+        ..Settings::default()
+    };
+
+    processor.process(&mut [samples.as_mut_slice()], None, &settings);
+
+    samples
+}
+
+#[test]
+fn pre_gain_starts_at_the_setting() {
+    let mut processor = Processor::new(SAMPLE_RATE);
+
+    assert!(
+        process(&mut processor, 2.0)
+            .iter()
+            .all(|sample| *sample == 1.0)
+    );
+}
+
+#[test]
+fn pre_gain_changes_smoothly() {
+    let mut processor = Processor::new(SAMPLE_RATE);
+
+    process(&mut processor, 1.0);
+    let ramp = process(&mut processor, 2.0);
+
+    assert!(ramp[0] < 0.6);
+    assert!(ramp.windows(2).all(|pair| pair[0] <= pair[1]));
+    assert!(ramp[LENGTH - 1] > 0.9);
+}
@@ -0,0 +1,102 @@
+//! Presets turn into the same settings in every program that reads them, with the shared defaults
+//! filling in what they leave out.
+
+#![cfg(feature = "preset")]
+
+use bit_flipper_core::preset::{Preset, PresetError};
+use bit_flipper_core::{
+    Choice, DecimateSettings, FreezeLength, GateAction, HistoryMode, Mode, Settings,
+    TransitionStyle, Waveform,
+};
+
+fn preset(mask: &str, mode: &str, params: &[(&str, f32)]) -> Preset {
+    Preset {
+        version: 1,
+        name: "Test".to_string(),
+        category: "Test".to_string(),
+        mask: mask.to_string(),
+        mode: mode.to_string(),
+        params: params.iter().map(|(id, v)| (id.to_string(), *v)).collect(),
+    }
+}
+
+#[test]
+fn empty_presets_get_the_defaults() {
+    let settings = preset("0x0", "Xor", &[]).settings().unwrap();
+
+    assert_eq!(settings, Settings::default());
+}
+
+#[test]
+fn stored_values_override_the_defaults() {
+    let settings = preset(
+        "0xffffffff0000ffff",
+        "and",
+        &[
+            ("decimate_on", 1.0),
+            ("decimate_rate", 4.0),
+            ("freeze_on", 1.0),
+            ("freeze_sync", 1.0),
+            ("freeze_division", 2.0),
+            ("transition_style", 2.0),
+        ],
+    )
+    .settings()
+    .unwrap();
+
+    // Single precision only keeps the lower 32 bits
+    assert_eq!(settings.mask, 0xffff);
+    assert_eq!(settings.mode, Mode::And);
+    assert_eq!(
+        settings.decimate,
+        Some(DecimateSettings {
+            rate: 4.0,
+            filter: true
+        })
+    );
+    assert_eq!(settings.freeze, Some(FreezeLength::Beats(1.0)));
+    assert_eq!(settings.transition, TransitionStyle::BitMorph);
+}
+
+#[test]
+fn invalid_masks_and_modes_are_errors() {
+    assert!(matches!(
+        preset("0xfg", "Xor", &[]).settings(),
+        Err(PresetError::InvalidMask(_))
+    ));
+    assert!(matches!(
+        preset("0x0", "Nand", &[]).settings(),
+        Err(PresetError::UnknownMode(_))
+    ));
+}
+
+#[test]
+fn choices_round_trip_through_their_index() {
+    fn check<T: Choice + std::fmt::Debug>() {
+        for (index, option) in T::OPTIONS.iter().enumerate() {
+            assert_eq!(option.index(), index);
+            assert_eq!(T::from_index(index), *option);
+        }
+
+        assert_eq!(T::from_index(T::OPTIONS.len()), T::default());
+    }
+
+    check::<Waveform>();
+    check::<HistoryMode>();
+    check::<TransitionStyle>();
+    check::<GateAction>();
+}
+
+#[test]
+fn factory_presets_load() {
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/../assets/presets");
+
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        let json = std::fs::read_to_string(&path).unwrap();
+
+        Preset::from_json(&json)
+            .and_then(|preset| preset.settings())
+            .unwrap_or_else(|err| panic!("{}: {err}", path.display()));
+    }
+}
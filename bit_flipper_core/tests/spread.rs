@@ -0,0 +1,45 @@
+//! Spreading the masks rotates the mantissa per channel, but leaves the LFE channel alone.
+
+use bit_flipper_core::{Mode, Processor, Settings};
+
+/// A 5.1 layout.
+const CHANNELS: usize = 6;
+const LFE_CHANNEL: usize = 3;
+const LENGTH: usize = 64;
+
+fn render(lfe_channel: Option<usize>) -> Vec<Vec<f32>> {
+    let mut processor = Processor::new(48000.0);
+    let settings = Settings {
+        mask: 0x0000_00ff,
+        mode: Mode::Xor,
+        spread: true,
+        lfe_channel,
+        ..Settings::default()
+    };
+
+    let mut channels: Vec<Vec<f32>> = (0..CHANNELS)
+        .map(|_| (0..LENGTH).map(|n| (n as f32 * 0.1).sin() * 0.5).collect())
+        .collect();
+    let mut slices: Vec<&mut [f32]> = channels.iter_mut().map(Vec::as_mut_slice).collect();
+
+    processor.process(&mut slices, None, &settings);
+
+    channels
+}
+
+#[test]
+fn spread_rotates_every_other_channel() {
+    let channels = render(None);
+
+    for channel in &channels[1..] {
+        assert_ne!(channel, &channels[0]);
+    }
+}
+
+#[test]
+fn spread_leaves_the_lfe_channel_alone() {
+    let channels = render(Some(LFE_CHANNEL));
+
+    assert_eq!(channels[LFE_CHANNEL], channels[0]);
+    assert_ne!(channels[LFE_CHANNEL + 1], channels[0]);
+}
@@ -0,0 +1,91 @@
+//! Crossfades between masks and modes neither click nor change the level. Linear ones keep the
+//! level of close results, equal-power ones the loudness of different results.
+
+use bit_flipper_core::{Mode, Processor, Settings, TransitionStyle};
+
+const SAMPLE_RATE: f32 = 48000.0;
+/// A quarter of the 10 ms transition.
+const BLOCK_SIZE: usize = 120;
+
+fn process(processor: &mut Processor, settings: &Settings) -> Vec<f32> {
+    let mut samples = vec![0.75; BLOCK_SIZE];
+    processor.process(&mut [samples.as_mut_slice()], None, settings);
+
+    samples
+}
+
+fn settings(mask: u64, mode: Mode) -> Settings {
+    Settings {
+        mask,
+        mode,
+        transition: TransitionStyle::Crossfade,
+        transition_ms: 10.0,
+        ..Settings::default()
+    }
+}
+
+#[test]
+fn interrupted_crossfade_is_continuous() {
+    let mut processor = Processor::new(SAMPLE_RATE);
+    let mut output = Vec::new();
+
+    // Flipping the top mantissa bits moves the constant input a long way each time
+    for mask in [0, 0x0060_0000, 0x0040_0000, 0x0020_0000] {
+        for _ in 0..2 {
+            output.extend(process(&mut processor, &settings(mask, Mode::Xor)));
+        }
+    }
+
+    let largest_step = output
+        .windows(2)
+        .map(|pair| (pair[1] - pair[0]).abs())
+        .fold(0.0, f32::max);
+
+    assert!(largest_step < 0.01, "output jumped by {largest_step}");
+}
+
+#[test]
+fn crossfade_keeps_the_level() {
+    let mut processor = Processor::new(SAMPLE_RATE);
+
+    // Without any bits set, both modes leave the input alone
+    process(&mut processor, &settings(0, Mode::Xor));
+    let output = process(&mut processor, &settings(0, Mode::Or));
+
+    assert!(output.iter().all(|sample| (sample - 0.75).abs() < 1e-6));
+}
+
+#[test]
+fn equal_power_crossfade_keeps_the_power() {
+    // Halfway through the 10 ms transition
+    const MIDDLE: usize = 239;
+
+    let silent = Settings {
+        transition: TransitionStyle::EqualPower,
+        ..settings(0, Mode::And)
+    };
+    let unchanged = Settings {
+        transition: TransitionStyle::EqualPower,
+        ..settings(0, Mode::Xor)
+    };
+
+    // Fading to and from silence leaves only the gain of the other side
+    let gain = |from: &Settings, to: &Settings| {
+        let mut processor = Processor::new(SAMPLE_RATE);
+        process(&mut processor, from);
+
+        let mut samples = Vec::new();
+        for _ in 0..4 {
+            samples.extend(process(&mut processor, to));
+        }
+
+        samples[MIDDLE] / 0.75
+    };
+
+    let fade_out = gain(&unchanged, &silent);
+    let fade_in = gain(&silent, &unchanged);
+    let power = fade_out * fade_out + fade_in * fade_in;
+
+    // The endpoints each have one side at full gain and the other silent
+    assert!((power - 1.0).abs() < 1e-3, "power in the middle is {power}");
+}
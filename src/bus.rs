@@ -1,11 +1,22 @@
+use atomic_float::AtomicF32;
 use crossbeam_channel::{Receiver, Sender, bounded};
 use nih_plug::buffer::Buffer;
+use std::sync::atomic::AtomicBool;
 
 pub const BUFFER_SIZE: usize = 2048;
 
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct Bus {
     channel: (Sender<f32>, Receiver<f32>),
+    pub meters: Meters,
+}
+
+/// Processing state the audio thread reports back to the editor.
+#[derive(Debug, Default)]
+pub struct Meters {
+    pub gate_closed: AtomicBool,
+    /// Deepest gain reduction of the last processed block, in decibels.
+    pub limiter_reduction: AtomicF32,
 }
 
 impl Default for Bus {
@@ -17,7 +28,10 @@ impl Default for Bus {
 impl Bus {
     pub fn new(size: usize) -> Self {
         let channel = bounded(size);
-        Self { channel }
+        Self {
+            channel,
+            meters: Meters::default(),
+        }
     }
 
     pub fn read(&self) -> Vec<f32> {
@@ -28,7 +42,8 @@ impl Bus {
         self.channel.0.try_send(value).ok();
     }
 
-    pub fn send_buffer_summing(&self, buffer: &mut Buffer) {
+    /// Sends a mono downmix of the buffer. The `lfe` channel, if any, is left out of it.
+    pub fn send_buffer_summing(&self, buffer: &mut Buffer, lfe: Option<usize>) {
         let channels = buffer.channels();
 
         if channels == 1 {
@@ -36,8 +51,17 @@ impl Bus {
                 self.send(*x.get_mut(0).unwrap());
             }
         } else {
+            let summed = channels - lfe.map_or(0, |_| 1);
+
             for mut x in buffer.iter_samples() {
-                self.send(x.iter_mut().map(|x| *x).sum::<f32>() / channels as f32);
+                let sum = x
+                    .iter_mut()
+                    .enumerate()
+                    .filter(|(channel, _)| Some(*channel) != lfe)
+                    .map(|(_, x)| *x)
+                    .sum::<f32>();
+
+                self.send(sum / summed as f32);
             }
         }
     }
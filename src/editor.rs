@@ -1,11 +1,16 @@
+use crate::BitFlipperParams;
 use crate::bus::Bus;
+use crate::editor::compare::AbCompare;
 use crate::editor::theme::{load_textures, open_theme_dir};
-use crate::model::FlipModes;
-use crate::{BitFlipperParams, UI_SCALE};
+use crate::editor::undo::UndoStack;
+use crate::model::{FlipModes, Waveform};
+use crate::preset::{self, install_factory_bank};
+use crate::settings::UserSettings;
 
 use boxi::prelude::*;
 use core::{CustomWgpuEditor, baseview_window_to_surface_target};
 use crossbeam::atomic::AtomicCell;
+use keyboard_types::{Key, KeyState, KeyboardEvent, Modifiers};
 use nih_plug::params::persist::PersistentField;
 use nih_plug::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -19,25 +24,40 @@ use wgpu::SurfaceTargetUnsafe;
 pub const VIEW_WIDTH: u16 = 200;
 pub const VIEW_HEIGHT: u16 = 200;
 
+mod compare;
 mod core;
 mod theme;
 mod ui;
+mod undo;
 
-fn downscale(pos: (f32, f32)) -> (i16, i16) {
-    (
-        (pos.0 / UI_SCALE as f32) as i16,
-        (pos.1 / UI_SCALE as f32) as i16,
-    )
+fn preset_browser(elements: &mut UiCollection<BitFlipperParams>) -> Option<&mut PresetBrowser> {
+    elements
+        .iter_mut()
+        .find_map(|el| el.as_any_mut().downcast_mut::<PresetBrowser>())
 }
 
 #[derive(Debug, Default)]
 pub struct EventStore {
     mouse_pos: (f32, f32),
+    /// Logical pixels per pixel of the view.
+    ui_scale: f32,
     drag_start: (f32, f32),
-    dragging_slider: bool,
+    /// The slider being dragged, with the normalized value the drag has moved it to so far. That
+    /// can fall between the steps of an integer parameter.
+    dragging_slider: Option<(SliderParam, f32)>,
     mouse_down: bool,
 }
 
+impl EventStore {
+    /// The mouse position in the view's pixels.
+    fn view_pos(&self) -> (i16, i16) {
+        (
+            (self.mouse_pos.0 / self.ui_scale) as i16,
+            (self.mouse_pos.1 / self.ui_scale) as i16,
+        )
+    }
+}
+
 pub struct CustomWgpuWindow {
     gui_context: Arc<dyn GuiContext>,
 
@@ -54,6 +74,7 @@ pub struct CustomWgpuWindow {
 
     params: Arc<BitFlipperParams>,
     event_store: EventStore,
+    undo: UndoStack,
 }
 
 impl CustomWgpuWindow {
@@ -116,7 +137,18 @@ impl CustomWgpuWindow {
             .await
             .expect("Failed to create device");
 
-        let (palette_texture, atlas_texture) = load_textures();
+        let settings = UserSettings::load();
+        if settings.hide_warning {
+            params
+                .editor_state
+                .warning_closed
+                .store(true, Ordering::Relaxed);
+        }
+
+        let (palette_texture, atlas_texture) = load_textures(settings.theme.as_deref());
+        if !settings.factory_bank_installed {
+            install_factory_bank();
+        }
 
         let surface_config = surface.get_default_config(&adapter, width, height).unwrap();
         surface.configure(&device, &surface_config);
@@ -136,6 +168,12 @@ impl CustomWgpuWindow {
             texture_atlas.clone(),
         ));
 
+        let font_pipe = Arc::new(StaticBoxPipeline::new(
+            &device,
+            tex_format,
+            Arc::new(font_atlas(&device, &queue)),
+        ));
+
         let color_pipeline = Arc::new(ColorBoxPipeline::new(&device, tex_format));
         let monitor_pipeline = Arc::new(SharedMonitorPipeline::new(&device, tex_format));
         let slide_pipe = Arc::new(SliderPipeline::new(
@@ -147,7 +185,7 @@ impl CustomWgpuWindow {
         let mut scene_elements = UiCollection::new();
 
         scene_elements.batch_append(vec![
-            Box::new(Background::new(bg_pipeline.clone())),
+            Box::new(Background::new(bg_pipeline.clone(), settings.animations)),
             Box::new(StaticBox::new(&device, &UV_gui_main, (46, 6), pipe.clone()).unwrap()),
             Box::new(StaticBox::new(&device, &UV_gui_monitors, (18, 154), pipe.clone()).unwrap()),
             Box::new(ModeButtonBuilder::new(&device, pipe.clone()).mode(FlipModes::Xor)),
@@ -160,12 +198,139 @@ impl CustomWgpuWindow {
                 [(20, 155), (105, 155)],
                 monitor_pipeline.clone(),
             )),
-            Box::new(DigitCluster::new(&device, pipe.clone())),
-            Box::new(Slider::new(&device, (74, 142), slide_pipe.clone())),
+            Box::new(DigitCluster::new(
+                &device,
+                pipe.clone(),
+                font_pipe.clone(),
+                color_pipeline.clone(),
+            )),
+            Box::new(CaptureBtn::new(&device, (88, 32), pipe.clone()).unwrap()),
+            Box::new(
+                Toggle::labeled(
+                    &device,
+                    (97, 31),
+                    "F64",
+                    font_pipe.clone(),
+                    color_pipeline.clone(),
+                    |p| &p.double_precision,
+                )
+                .unwrap(),
+            ),
+            Box::new(Slider::new(&device, (74, 142), slide_pipe.clone(), |p| {
+                &p.pre_gain
+            })),
+            Box::new(
+                Toggle::new(&device, (139, 4), pipe.clone(), |p| &p.generator.enabled).unwrap(),
+            ),
+            Box::new(
+                Label::<3>::new(&device, (150, 5), font_pipe.clone())
+                    .unwrap()
+                    .with_text("GEN"),
+            ),
+            Box::new(
+                Selector::new(&device, (166, 5), font_pipe.clone(), |p| {
+                    &p.generator.waveform
+                })
+                .unwrap(),
+            ),
+            Box::new(Slider::new(&device, (138, 14), slide_pipe.clone(), |p| {
+                &p.generator.frequency
+            })),
+            Box::new(Slider::new(&device, (138, 24), slide_pipe.clone(), |p| {
+                &p.generator.level
+            })),
+            Box::new(
+                Toggle::new(&device, (139, 40), pipe.clone(), |p| &p.decimator.enabled).unwrap(),
+            ),
+            Box::new(
+                Label::<8>::new(&device, (150, 41), font_pipe.clone())
+                    .unwrap()
+                    .with_text("DEC RATE"),
+            ),
+            Box::new(
+                Toggle::labeled(
+                    &device,
+                    (186, 41),
+                    "FLT",
+                    font_pipe.clone(),
+                    color_pipeline.clone(),
+                    |p| &p.decimator.filter,
+                )
+                .unwrap(),
+            ),
+            Box::new(Slider::new(&device, (138, 50), slide_pipe.clone(), |p| {
+                &p.decimator.rate
+            })),
+            Box::new(
+                Toggle::new(&device, (139, 66), pipe.clone(), |p| &p.truncate.enabled).unwrap(),
+            ),
+            Box::new(
+                Label::<10>::new(&device, (150, 67), font_pipe.clone())
+                    .unwrap()
+                    .with_text("TRUNC BITS"),
+            ),
+            Box::new(Slider::int(&device, (138, 76), slide_pipe.clone(), |p| {
+                &p.truncate.depth
+            })),
+            Box::new(Toggle::new(&device, (139, 92), pipe.clone(), |p| &p.gate.enabled).unwrap()),
+            Box::new(
+                Label::<8>::new(&device, (150, 93), font_pipe.clone())
+                    .unwrap()
+                    .with_text("GATE THR"),
+            ),
+            Box::new(
+                Toggle::labeled(
+                    &device,
+                    (184, 93),
+                    "SC",
+                    font_pipe.clone(),
+                    color_pipeline.clone(),
+                    |p| &p.gate.sidechain,
+                )
+                .unwrap(),
+            ),
+            Box::new(Led::new(
+                &device,
+                (195, 93),
+                color_pipeline.clone(),
+                bus.clone(),
+                |m| m.gate_closed.load(Ordering::Relaxed),
+            )),
+            Box::new(Slider::new(&device, (138, 102), slide_pipe.clone(), |p| {
+                &p.gate.threshold
+            })),
+            Box::new(
+                Toggle::new(&device, (139, 118), pipe.clone(), |p| &p.limiter.enabled).unwrap(),
+            ),
+            Box::new(
+                Label::<8>::new(&device, (150, 119), font_pipe.clone())
+                    .unwrap()
+                    .with_text("LIM CEIL"),
+            ),
+            Box::new(Slider::new(&device, (138, 128), slide_pipe.clone(), |p| {
+                &p.limiter.ceiling
+            })),
+            Box::new(ReductionText::new(&device, (138, 138), pipe.clone(), bus.clone()).unwrap()),
             Box::new(VolumeText::new(&device, (74, 142), pipe.clone()).unwrap()),
-            Box::new(Warning::new(&device, (40, 40), pipe.clone(), color_pipeline).unwrap()),
+            Box::new(
+                SnapshotSlots::new(&device, (2, 28), pipe.clone(), color_pipeline.clone()).unwrap(),
+            ),
+            Box::new(
+                CompareBar::new(&device, (2, 52), font_pipe.clone(), color_pipeline.clone())
+                    .unwrap(),
+            ),
+            Box::new(
+                PresetBrowser::new(&device, (2, 8), font_pipe.clone(), color_pipeline.clone())
+                    .unwrap(),
+            ),
         ]);
 
+        if !params.editor_state.warning_closed.load(Ordering::Relaxed) {
+            scene_elements.batch_append(vec![Box::new(
+                Warning::new(&device, (40, 40), pipe.clone(), font_pipe, color_pipeline).unwrap(),
+            )]);
+        }
+
         let grayscale_texture = device.create_texture(&wgpu::TextureDescriptor {
             label: Some("Grayscale Render Target"),
             size: wgpu::Extent3d {
@@ -205,8 +370,52 @@ impl CustomWgpuWindow {
             bus,
             //
             params,
-            event_store: EventStore::default(),
+            event_store: EventStore {
+                ui_scale: unscaled_width as f32 / VIEW_WIDTH as f32,
+                ..Default::default()
+            },
+            undo: UndoStack::default(),
+        }
+    }
+
+    /// Applies what the preset browser asked for. Returns whether the browser used up the event.
+    fn respond(&mut self, response: BrowserResponse) -> bool {
+        match response {
+            BrowserResponse::Ignored => false,
+            BrowserResponse::Handled => true,
+            BrowserResponse::Load(loaded) => {
+                let result = preset::apply(&loaded, &self.params, self.gui_context.as_ref());
+
+                if let Err(err) = result
+                    && let Some(browser) = preset_browser(&mut self.scene_elements)
+                {
+                    browser.fail(&err);
+                }
+
+                true
+            }
+        }
+    }
+
+    /// Handles Ctrl+Z and Ctrl+Shift+Z, or Cmd on macOS. Returns whether the event was one of them.
+    fn undo_shortcut(&mut self, event: &KeyboardEvent) -> bool {
+        let is_z = matches!(&event.key, Key::Character(ch) if ch.eq_ignore_ascii_case("z"));
+        let command = event
+            .modifiers
+            .intersects(Modifiers::CONTROL | Modifiers::META);
+
+        if !is_z || !command {
+            return false;
+        }
+
+        if event.state == KeyState::Down {
+            match event.modifiers.contains(Modifiers::SHIFT) {
+                true => self.undo.redo(&self.params, self.gui_context.as_ref()),
+                false => self.undo.undo(&self.params, self.gui_context.as_ref()),
+            }
         }
+
+        true
     }
 }
 
@@ -291,12 +500,35 @@ impl baseview::WindowHandler for CustomWgpuWindow {
             baseview::Event::Mouse(event) => match event {
                 baseview::MouseEvent::ButtonPressed {
                     button: baseview::MouseButton::Left,
-                    modifiers: _,
+                    modifiers,
                 } => {
-                    self.event_store.dragging_slider = false;
+                    self.event_store.dragging_slider = None;
                     self.event_store.mouse_down = true;
+                    self.undo.begin(&self.params);
                     self.event_store.drag_start = self.event_store.mouse_pos;
 
+                    let warning_closed = self
+                        .params
+                        .editor_state
+                        .warning_closed
+                        .load(Ordering::Relaxed);
+
+                    // The browser goes first, its list covers everything else while it's open
+                    if warning_closed
+                        && let Some(browser) = preset_browser(&mut self.scene_elements)
+                    {
+                        let pos = self.event_store.view_pos();
+                        let response = browser.click(pos, &self.params);
+
+                        if self.respond(response) {
+                            return baseview::EventStatus::Captured;
+                        }
+                    }
+
+                    // A recalled snapshot overrides the mask bits and the mode, so editing those
+                    // would have no audible effect
+                    let recalled = self.params.snapshot.recalled().is_some();
+
                     for el in self.scene_elements.iter_mut() {
                         if self
                             .params
@@ -304,10 +536,9 @@ impl baseview::WindowHandler for CustomWgpuWindow {
                             .warning_closed
                             .load(Ordering::Relaxed)
                         {
-                            if let Some(btn) = el.as_mut().as_any_mut().downcast_mut::<Button>() {
-                                let mouse_pos = self.event_store.mouse_pos;
-
-                                if btn.is_mouse_over(downscale(mouse_pos)) {
+                            if let Some(btn) = el.as_mut().as_any_mut().downcast_mut::<ModeButton>()
+                            {
+                                if !recalled && btn.is_mouse_over(self.event_store.view_pos()) {
                                     let setter = ParamSetter::new(&*self.gui_context);
                                     let norm = self.params.mode.preview_normalized(btn.get_state());
 
@@ -320,8 +551,26 @@ impl baseview::WindowHandler for CustomWgpuWindow {
                             }
 
                             if let Some(cluster) = el.as_any_mut().downcast_mut::<DigitCluster>() {
-                                for digit in cluster.digits.iter_mut() {
-                                    if digit.is_mouse_over(downscale(self.event_store.mouse_pos)) {
+                                let page_toggle = &mut cluster.page_toggle;
+                                if page_toggle.is_visible()
+                                    && page_toggle.is_mouse_over(self.event_store.view_pos())
+                                {
+                                    page_toggle.next_page();
+                                }
+
+                                let op = cluster.fields.click(self.event_store.view_pos());
+                                if let Some(op) = op.filter(|_| !recalled) {
+                                    let double_precision = self.params.double_precision.value();
+                                    let mask =
+                                        op.apply(self.params.bits.to_u64(), double_precision);
+                                    let setter = ParamSetter::new(&*self.gui_context);
+
+                                    self.params.bits.set_mask(&setter, mask);
+                                }
+
+                                for digit in cluster.digits() {
+                                    if !recalled && digit.is_mouse_over(self.event_store.view_pos())
+                                    {
                                         if let Some(param) =
                                             self.params.bits.get_bit_param(digit.id())
                                         {
@@ -338,10 +587,95 @@ impl baseview::WindowHandler for CustomWgpuWindow {
                                 }
                             }
 
+                            if let Some(slots) = el.as_any_mut().downcast_mut::<SnapshotSlots>() {
+                                let mouse_pos = self.event_store.view_pos();
+                                let snapshot = &self.params.snapshot;
+
+                                for btn in slots.buttons.iter() {
+                                    if !btn.is_mouse_over(mouse_pos) {
+                                        continue;
+                                    }
+
+                                    if modifiers.contains(Modifiers::SHIFT) {
+                                        snapshot.store(btn.get_state(), self.params.source());
+                                    } else {
+                                        // Clicking the recalled slot again goes back to the mask bits
+                                        let slot = match snapshot.slot.value() == btn.get_state() {
+                                            true => 0,
+                                            false => btn.get_state(),
+                                        };
+
+                                        let setter = ParamSetter::new(&*self.gui_context);
+
+                                        setter.begin_set_parameter(&snapshot.slot);
+                                        setter.set_parameter(&snapshot.slot, slot);
+                                        setter.end_set_parameter(&snapshot.slot);
+                                    }
+                                }
+
+                                continue;
+                            }
+
+                            if let Some(bar) = el.as_any_mut().downcast_mut::<CompareBar>() {
+                                let mouse_pos = self.event_store.view_pos();
+                                let compare = &self.params.editor_state.compare;
+
+                                if bar
+                                    .slot_at(mouse_pos)
+                                    .is_some_and(|s| s != compare.active())
+                                {
+                                    compare.switch(&self.params, self.gui_context.as_ref());
+                                } else if bar.copy.is_mouse_over(mouse_pos) {
+                                    compare.copy_to_other(&self.params);
+                                }
+
+                                continue;
+                            }
+
                             if let Some(slider) = el.as_mut().as_any_mut().downcast_mut::<Slider>()
                             {
-                                if slider.is_mouse_over(downscale(self.event_store.mouse_pos)) {
-                                    self.event_store.dragging_slider = true
+                                if slider.is_mouse_over(self.event_store.view_pos()) {
+                                    let param = slider.param();
+                                    let value = param.normalized_value(&self.params);
+
+                                    self.event_store.dragging_slider = Some((param, value));
+                                }
+
+                                continue;
+                            }
+
+                            if let Some(selector) = el
+                                .as_mut()
+                                .as_any_mut()
+                                .downcast_mut::<Selector<Waveform>>()
+                            {
+                                if selector.is_mouse_over(self.event_store.view_pos()) {
+                                    let setter = ParamSetter::new(&*self.gui_context);
+                                    selector.next(&self.params, &setter);
+                                }
+
+                                continue;
+                            }
+
+                            if let Some(toggle) = el.as_mut().as_any_mut().downcast_mut::<Toggle>()
+                            {
+                                if toggle.is_mouse_over(self.event_store.view_pos()) {
+                                    let param = toggle.param()(&self.params);
+                                    let setter = ParamSetter::new(&*self.gui_context);
+                                    let norm = param.preview_normalized(!param.value());
+
+                                    setter.begin_set_parameter(param);
+                                    setter.set_parameter_normalized(param, norm);
+                                    setter.end_set_parameter(param);
+                                }
+
+                                continue;
+                            }
+
+                            if let Some(btn) = el.as_mut().as_any_mut().downcast_mut::<CaptureBtn>()
+                            {
+                                if btn.is_mouse_over(self.event_store.view_pos()) {
+                                    CaptureBtn::capture(&self.params);
                                 }
 
                                 continue;
@@ -350,54 +684,97 @@ impl baseview::WindowHandler for CustomWgpuWindow {
                             if let Some(btn) =
                                 el.as_mut().as_any_mut().downcast_mut::<OpenFolderBtn>()
                             {
-                                if btn.is_mouse_over(downscale(self.event_store.mouse_pos)) {
+                                if btn.is_mouse_over(self.event_store.view_pos()) {
                                     open_theme_dir().ok();
                                 }
                             }
-                        } else if let Some(btn) = el.as_any_mut().downcast_mut::<Warning>() {
-                            let mouse_pos = self.event_store.mouse_pos;
+                        } else if let Some(warning) = el.as_any_mut().downcast_mut::<Warning>() {
+                            let mouse_pos = self.event_store.view_pos();
+
+                            if warning.is_mouse_over(mouse_pos) {
+                                if warning.hide() {
+                                    UserSettings::update(|s| s.hide_warning = true).ok();
+                                }
 
-                            if btn.is_mouse_over(downscale(mouse_pos)) {
                                 self.params.editor_state.warning_closed.set(true);
+                            } else if warning.dont_show_again.is_mouse_over(mouse_pos) {
+                                warning.toggle_hide();
                             }
                         }
                     }
                 }
+                baseview::MouseEvent::WheelScrolled {
+                    delta,
+                    modifiers: _,
+                } => {
+                    if let Some(browser) = preset_browser(&mut self.scene_elements)
+                        && browser.is_open()
+                    {
+                        let (baseview::ScrollDelta::Lines { y, .. }
+                        | baseview::ScrollDelta::Pixels { y, .. }) = delta;
+
+                        browser.scroll(-y.signum() as i32);
+                    }
+                }
                 baseview::MouseEvent::ButtonReleased {
                     button: baseview::MouseButton::Left,
                     modifiers: _,
-                } => self.event_store.mouse_down = false,
+                } => {
+                    self.event_store.mouse_down = false;
+                    self.undo.end(&self.params);
+                }
                 baseview::MouseEvent::CursorMoved {
                     position,
                     modifiers: _,
                 } => {
                     self.event_store.mouse_pos = (position.x as f32, position.y as f32);
 
-                    if self.event_store.dragging_slider && self.event_store.mouse_down {
+                    if let Some((param, value)) = &mut self.event_store.dragging_slider
+                        && self.event_store.mouse_down
+                    {
                         let delta = self.event_store.mouse_pos.0 - self.event_store.drag_start.0;
                         self.event_store.drag_start.0 = self.event_store.mouse_pos.0;
 
                         let slider_width = 59.0;
                         let delta_norm = delta / slider_width;
 
-                        let param = &self.params.pre_gain;
-                        let new_norm =
-                            (param.preview_normalized(param.value()) + delta_norm).clamp(0.0, 1.0);
+                        *value = (*value + delta_norm).clamp(0.0, 1.0);
 
                         let setter = ParamSetter::new(&*self.gui_context);
-
-                        setter.begin_set_parameter(param);
-                        setter.set_parameter_normalized(param, new_norm);
-                        setter.end_set_parameter(param);
+                        param.set_normalized(&self.params, &setter, *value);
                     }
                 }
                 _ => {}
             },
+            baseview::Event::Keyboard(event) => {
+                // Loading a preset from the list gets its own entry, unless a drag is going on
+                let record = !self.event_store.mouse_down;
+                if record {
+                    self.undo.begin(&self.params);
+                }
+
+                let response = match preset_browser(&mut self.scene_elements) {
+                    Some(browser) => browser.key(event, &self.params),
+                    None => BrowserResponse::Ignored,
+                };
+
+                let handled = self.respond(response);
+                if record {
+                    self.undo.end(&self.params);
+                }
+
+                // Lets the host have the keys the editor doesn't use
+                if !handled && !self.undo_shortcut(event) {
+                    return baseview::EventStatus::Ignored;
+                }
+            }
             baseview::Event::Window(baseview::WindowEvent::Resized(window_info)) => {
-                self.params.editor_state.size.store((
+                self.params.editor_state.size.store(Some((
                     window_info.logical_size().width.round() as u32,
                     window_info.logical_size().height.round() as u32,
-                ));
+                )));
+                self.event_store.ui_scale =
+                    window_info.logical_size().width as f32 / VIEW_WIDTH as f32;
 
                 self.surface_config.width = window_info.physical_size().width;
                 self.surface_config.height = window_info.physical_size().height;
@@ -423,29 +800,48 @@ impl baseview::WindowHandler for CustomWgpuWindow {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CustomWgpuEditorState {
-    /// The window's size in logical pixels before applying `scale_factor`.
+    /// The window's size in logical pixels before applying `scale_factor`. Left unset until the
+    /// host asks for it, so instances without an editor never read the settings file.
     #[serde(with = "nih_plug::params::persist::serialize_atomic_cell")]
-    size: AtomicCell<(u32, u32)>,
+    size: AtomicCell<Option<(u32, u32)>>,
     /// Whether the editor's window is currently open.
     #[serde(skip)]
     open: AtomicBool,
 
     #[serde(skip)]
     warning_closed: AtomicBool,
+
+    /// Set whenever a parameter changes, for the widgets that only need to update then.
+    #[serde(skip)]
+    params_changed: AtomicBool,
+
+    #[serde(skip)]
+    compare: AbCompare,
 }
 
 impl CustomWgpuEditorState {
-    pub fn from_size(size: (u32, u32)) -> Arc<Self> {
+    pub fn new() -> Arc<Self> {
         Arc::new(Self {
-            size: AtomicCell::new(size),
+            size: AtomicCell::new(None),
             open: AtomicBool::new(false),
             warning_closed: AtomicBool::new(false),
+            params_changed: AtomicBool::new(true),
+            compare: AbCompare::default(),
         })
     }
 
-    /// Returns a `(width, height)` pair for the current size of the GUI in logical pixels.
+    /// Returns a `(width, height)` pair for the current size of the GUI in logical pixels. The
+    /// first time, unless a saved state set it, that's the size from the user's `ui_scale`.
     pub fn size(&self) -> (u32, u32) {
-        self.size.load()
+        if let Some(size) = self.size.load() {
+            return size;
+        }
+
+        let ui_scale = UserSettings::load().ui_scale;
+        let size = (VIEW_WIDTH as u32 * ui_scale, VIEW_HEIGHT as u32 * ui_scale);
+        self.size.store(Some(size));
+
+        size
     }
 
     /// Whether the GUI is currently visible.
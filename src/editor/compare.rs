@@ -0,0 +1,110 @@
+use crate::BitFlipperParams;
+use crate::state::sound_fields;
+
+use nih_plug::prelude::*;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum AbSlot {
+    #[default]
+    A,
+    B,
+}
+
+impl AbSlot {
+    pub fn other(self) -> Self {
+        match self {
+            Self::A => Self::B,
+            Self::B => Self::A,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::A => "A",
+            Self::B => "B",
+        }
+    }
+}
+
+/// Everything a slot holds. Unlike a preset this includes the recalled snapshot slot and the
+/// persisted fields, so the snapshot slots and mask B switch along with the rest.
+#[derive(Debug, Clone, PartialEq)]
+struct SlotState {
+    /// The unmodulated normalized value of every parameter, by ID.
+    values: BTreeMap<String, f32>,
+    fields: BTreeMap<String, String>,
+}
+
+impl SlotState {
+    fn capture(params: &BitFlipperParams) -> Self {
+        let values = params
+            .param_map()
+            .into_iter()
+            // SAFETY: The pointers point into `params`, which outlives this function
+            .map(|(id, ptr, _)| (id, unsafe { ptr.unmodulated_normalized_value() }))
+            .collect();
+
+        Self {
+            values,
+            fields: sound_fields(params),
+        }
+    }
+
+    fn apply(&self, params: &BitFlipperParams, context: &dyn GuiContext) {
+        for (id, ptr, _) in params.param_map() {
+            let Some(value) = self.values.get(&id) else {
+                continue;
+            };
+
+            // SAFETY: The pointers point into `params`, which outlives this function
+            unsafe {
+                context.raw_begin_set_parameter(ptr);
+                context.raw_set_parameter_normalized(ptr, *value);
+                context.raw_end_set_parameter(ptr);
+            }
+        }
+
+        params.deserialize_fields(&self.fields);
+    }
+}
+
+#[derive(Debug, Default)]
+struct CompareState {
+    active: AbSlot,
+    /// The state of the slot that isn't active. Empty until the first switch, so B starts out as
+    /// a copy of A.
+    other: Option<SlotState>,
+}
+
+/// Two complete plugin states to compare while tweaking. The active one lives in the parameters,
+/// switching stores it and applies the other one.
+#[derive(Debug, Default)]
+pub struct AbCompare {
+    state: Mutex<CompareState>,
+}
+
+impl AbCompare {
+    pub fn active(&self) -> AbSlot {
+        self.state.lock().unwrap().active
+    }
+
+    /// Applies the other slot as one gesture per parameter, so the host can record the switch.
+    pub fn switch(&self, params: &BitFlipperParams, context: &dyn GuiContext) {
+        let mut state = self.state.lock().unwrap();
+        let current = SlotState::capture(params);
+
+        if let Some(other) = state.other.take() {
+            other.apply(params, context);
+        }
+
+        state.other = Some(current);
+        state.active = state.active.other();
+    }
+
+    /// Overwrites the other slot with the active one.
+    pub fn copy_to_other(&self, params: &BitFlipperParams) {
+        self.state.lock().unwrap().other = Some(SlotState::capture(params));
+    }
+}
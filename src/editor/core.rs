@@ -93,14 +93,23 @@ impl Editor for CustomWgpuEditor {
 
     fn param_value_changed(&self, _id: &str, _normalized_value: f32) {
         // As mentioned above, for now we'll always force a redraw to allow meter widgets to work
-        // correctly. In the future we can use an `Arc<AtomicBool>` and only force a redraw when
-        // that boolean is set.
+        // correctly. Only the preset browser's dirty check waits for a change.
+        self.params
+            .editor_state
+            .params_changed
+            .store(true, Ordering::Relaxed);
     }
 
-    fn param_modulation_changed(&self, _id: &str, _modulation_offset: f32) {}
+    fn param_modulation_changed(&self, _id: &str, _modulation_offset: f32) {
+        // Same, the widgets read the modulated values while redrawing. The slider shows them as a
+        // ghost handle and the digits light up where modulation flips a bit.
+    }
 
     fn param_values_changed(&self) {
-        // Same
+        self.params
+            .editor_state
+            .params_changed
+            .store(true, Ordering::Relaxed);
     }
 }
 
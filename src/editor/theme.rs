@@ -1,10 +1,18 @@
+use crate::settings::config_dir;
+
 use std::borrow::Cow;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 const DEFAULT_PALETTE: &[u8] = include_bytes!("../../assets/textures/__palette__.png");
 const DEFAULT_ATLAS: &[u8] = include_bytes!("../../assets/textures/__texture_atlas__.png");
 
+const PALETTE_FILE: &str = "__palette__.png";
+const ATLAS_FILE: &str = "__texture_atlas__.png";
+
+/// Holds one folder per named theme.
+const THEMES_DIR: &str = "themes";
+
 fn get_or_write(tex_path: &PathBuf, fallback: &'static [u8]) -> Cow<'static, [u8]> {
     if !tex_path.exists() {
         fs::write(tex_path, fallback).ok();
@@ -18,14 +26,22 @@ fn get_or_write(tex_path: &PathBuf, fallback: &'static [u8]) -> Cow<'static, [u8
     }
 }
 
-pub fn load_textures() -> (Cow<'static, [u8]>, Cow<'static, [u8]>) {
-    let config_dir = dirs::config_dir();
-    if let Some(tex_path) = config_dir.map(|p| p.join(env!("CARGO_PKG_NAME"))) {
-        let atlas_path = tex_path.join("__texture_atlas__.png");
-        let palette_path = tex_path.join("__palette__.png");
+/// A named theme only needs the files it changes.
+fn read_or(path: &Path, fallback: Cow<'static, [u8]>) -> Cow<'static, [u8]> {
+    fs::read(path).map(Cow::Owned).unwrap_or(fallback)
+}
 
-        let atlas = get_or_write(&atlas_path, DEFAULT_ATLAS);
-        let palette = get_or_write(&palette_path, DEFAULT_PALETTE);
+pub fn load_textures(theme: Option<&str>) -> (Cow<'static, [u8]>, Cow<'static, [u8]>) {
+    if let Some(tex_path) = config_dir() {
+        fs::create_dir_all(&tex_path).ok();
+
+        let mut atlas = get_or_write(&tex_path.join(ATLAS_FILE), DEFAULT_ATLAS);
+        let mut palette = get_or_write(&tex_path.join(PALETTE_FILE), DEFAULT_PALETTE);
+
+        if let Some(theme_path) = theme.map(|name| tex_path.join(THEMES_DIR).join(name)) {
+            atlas = read_or(&theme_path.join(ATLAS_FILE), atlas);
+            palette = read_or(&theme_path.join(PALETTE_FILE), palette);
+        }
 
         (palette, atlas)
     } else {
@@ -34,8 +50,7 @@ pub fn load_textures() -> (Cow<'static, [u8]>, Cow<'static, [u8]>) {
 }
 
 pub fn open_theme_dir() -> std::io::Result<()> {
-    let config_dir = dirs::config_dir();
-    if let Some(tex_path) = config_dir.map(|p| p.join(env!("CARGO_PKG_NAME"))) {
+    if let Some(tex_path) = config_dir() {
         if tex_path.exists() {
             open::that(tex_path)?;
         }
@@ -1,25 +1,48 @@
+use crate::BitFlipperParams;
+
 mod background;
 mod button;
+mod capture;
 mod color_box;
+mod compare;
 mod digit;
+mod fields;
+mod font;
+mod led;
 mod monitor;
 mod open_folder;
 mod postprocess;
+mod preset_browser;
+mod selector;
 mod slider;
+mod snapshot;
 mod static_box;
 mod text;
+mod toggle;
 mod warning;
 
 pub(super) mod texture;
 
 pub use background::*;
 pub use button::*;
+pub use capture::*;
 pub use color_box::*;
+pub use compare::*;
 pub use digit::*;
+pub use fields::*;
+pub use font::font_atlas;
+pub use led::*;
 pub use monitor::*;
 pub use open_folder::*;
 pub use postprocess::*;
+pub use preset_browser::*;
+pub use selector::*;
 pub use slider::*;
+pub use snapshot::*;
 pub use static_box::*;
 pub use text::*;
+pub use toggle::*;
 pub use warning::*;
+
+/// Picks the parameter a generic widget is bound to out of the plugin's parameters.
+pub type ParamGetter<P> = fn(&BitFlipperParams) -> &P;
@@ -26,6 +26,8 @@ pub struct BackgroundPipeline {
 
 pub struct Background {
     shared_pipeline: Arc<BackgroundPipeline>,
+    /// Stands still otherwise.
+    animated: bool,
 }
 
 impl BackgroundPipeline {
@@ -137,9 +139,10 @@ impl BackgroundPipeline {
 }
 
 impl Background {
-    pub fn new(pipeline: Arc<BackgroundPipeline>) -> Self {
+    pub fn new(pipeline: Arc<BackgroundPipeline>, animated: bool) -> Self {
         Self {
             shared_pipeline: pipeline,
+            animated,
         }
     }
 }
@@ -151,7 +154,10 @@ impl UiElement<BitFlipperParams> for Background {
         _params: Arc<crate::BitFlipperParams>,
         _buffer: &[f32],
     ) {
-        let time = self.shared_pipeline.start_time.elapsed().as_secs_f32();
+        let time = match self.animated {
+            true => self.shared_pipeline.start_time.elapsed().as_secs_f32(),
+            false => 0.0,
+        };
         let updated = BackgroundUniforms {
             uv_region: self
                 .shared_pipeline
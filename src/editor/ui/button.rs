@@ -1,4 +1,4 @@
-use super::{StaticBox, StaticBoxPipeline, texture::TextureError};
+use super::{ColorBox, StaticBox, StaticBoxPipeline, texture::TextureError};
 
 use crate::BitFlipperParams;
 use crate::editor::texture::UVSegment::{self, *};
@@ -7,16 +7,27 @@ use crate::model::FlipModes;
 use boxi::prelude::*;
 use std::sync::Arc;
 
-pub struct Button {
+/// Lights up while the value read from the parameters equals the one the button stands for.
+pub struct Button<T> {
     static_box: StaticBox,
-    state: FlipModes,
+    state: T,
+    /// Reads the value the button compares against.
+    current: fn(&BitFlipperParams) -> T,
+    /// Keeps the button visible while it's off, dimmed by this box.
+    dim: Option<ColorBox>,
     is_on: bool,
 }
 
-impl Button {
+pub type ModeButton = Button<FlipModes>;
+
+/// Recalls a snapshot slot, counting from 1.
+pub type SlotButton = Button<i32>;
+
+impl<T: Copy + PartialEq> Button<T> {
     pub fn new(
         device: &wgpu::Device,
-        flip_mode: FlipModes,
+        state: T,
+        current: fn(&BitFlipperParams) -> T,
         uv_segment: &UVSegment,
         position: (u16, u16),
         pipeline: Arc<StaticBoxPipeline>,
@@ -25,23 +36,35 @@ impl Button {
 
         Ok(Self {
             is_on: false,
-            state: flip_mode,
+            state,
+            current,
+            dim: None,
             static_box,
         })
     }
 
-    pub fn get_state(&self) -> FlipModes {
+    /// Shows the button dimmed by `dim` while it's off, instead of hiding it.
+    pub fn with_dim(mut self, dim: ColorBox) -> Self {
+        self.dim = Some(dim);
+        self
+    }
+
+    pub fn dim(&self) -> Option<&ColorBox> {
+        self.dim.as_ref()
+    }
+
+    pub fn get_state(&self) -> T {
         self.state
     }
 }
 
-impl UiBox for Button {
+impl<T> UiBox for Button<T> {
     fn width(&self) -> u16 {
-        16
+        self.static_box.width()
     }
 
     fn height(&self) -> u16 {
-        16
+        self.static_box.height()
     }
 
     fn position(&self) -> (u16, u16) {
@@ -49,20 +72,25 @@ impl UiBox for Button {
     }
 }
 
-impl UiInteractive<BitFlipperParams> for Button {}
-impl UiElement<BitFlipperParams> for Button {
+impl<T: Copy + PartialEq + 'static> UiInteractive<BitFlipperParams> for Button<T> {}
+impl<T: Copy + PartialEq + 'static> UiElement<BitFlipperParams> for Button<T> {
     fn prerender(
         &mut self,
         _queue: &wgpu::Queue,
         params: Arc<crate::BitFlipperParams>,
         _buffer: &[f32],
     ) {
-        self.is_on = self.state == params.mode.value()
+        self.is_on = self.state == (self.current)(&params)
     }
 
     fn render(&self, render_pass: &mut wgpu::RenderPass) {
-        if self.is_on {
-            self.static_box.render(render_pass);
+        match (&self.dim, self.is_on) {
+            (_, true) => self.static_box.render(render_pass),
+            (Some(dim), false) => {
+                self.static_box.render(render_pass);
+                dim.render(render_pass);
+            }
+            (None, false) => {}
         }
     }
 }
@@ -80,13 +108,22 @@ impl<'a> ModeButtonBuilder<'a> {
         }
     }
 
-    pub fn mode(&self, mode: FlipModes) -> Button {
-        match mode {
-            FlipModes::Xor => Button::new(self.d, mode, &UV_btn_xor, (46, 51), self.p.clone()),
-            FlipModes::Or => Button::new(self.d, mode, &UV_btn_or, (46, 68), self.p.clone()),
-            FlipModes::And => Button::new(self.d, mode, &UV_btn_and, (46, 85), self.p.clone()),
-            FlipModes::Not => Button::new(self.d, mode, &UV_btn_not, (46, 102), self.p.clone()),
-        }
+    pub fn mode(&self, mode: FlipModes) -> ModeButton {
+        let (uv_segment, position) = match mode {
+            FlipModes::Xor => (&UV_btn_xor, (46, 51)),
+            FlipModes::Or => (&UV_btn_or, (46, 68)),
+            FlipModes::And => (&UV_btn_and, (46, 85)),
+            FlipModes::Not => (&UV_btn_not, (46, 102)),
+        };
+
+        Button::new(
+            self.d,
+            mode,
+            |p| p.mode(),
+            uv_segment,
+            position,
+            self.p.clone(),
+        )
         .unwrap()
     }
 }
@@ -0,0 +1,44 @@
+use crate::BitFlipperParams;
+use crate::editor::texture::{TextureError, UVSegment::*};
+use crate::editor::ui::{StaticBox, StaticBoxPipeline};
+
+use boxi::prelude::*;
+use std::sync::Arc;
+
+/// Stores the current mask bits as mask B, the target of the morph macro.
+pub struct CaptureBtn(StaticBox);
+
+impl CaptureBtn {
+    pub fn new(
+        device: &wgpu::Device,
+        position: (u16, u16),
+        pipeline: Arc<StaticBoxPipeline>,
+    ) -> Result<Self, TextureError> {
+        Ok(Self(StaticBox::new(device, &UV_num_B, position, pipeline)?))
+    }
+
+    pub fn capture(params: &BitFlipperParams) {
+        params.macros.mask_b.store(params.mask_bits());
+    }
+}
+
+impl UiInteractive<BitFlipperParams> for CaptureBtn {}
+impl UiElement<BitFlipperParams> for CaptureBtn {
+    fn render(&self, render_pass: &mut wgpu::RenderPass) {
+        self.0.render(render_pass);
+    }
+}
+
+impl UiBox for CaptureBtn {
+    fn width(&self) -> u16 {
+        self.0.width()
+    }
+
+    fn height(&self) -> u16 {
+        self.0.height()
+    }
+
+    fn position(&self) -> (u16, u16) {
+        self.0.position()
+    }
+}
@@ -18,6 +18,7 @@ pub struct ColorBox {
     width: u16,
     height: u16,
     vertex_buffer: wgpu::Buffer,
+    color_buffer: wgpu::Buffer,
     color_bind_group: wgpu::BindGroup,
 }
 
@@ -124,9 +125,14 @@ impl ColorBox {
             width,
             height,
             vertex_buffer,
+            color_buffer,
             color_bind_group,
         }
     }
+
+    pub fn set_color(&self, queue: &wgpu::Queue, color: [f32; 4]) {
+        queue.write_buffer(&self.color_buffer, 0, bytemuck::cast_slice(&color));
+    }
 }
 
 impl UiElement<BitFlipperParams> for ColorBox {
@@ -0,0 +1,110 @@
+use crate::BitFlipperParams;
+use crate::editor::compare::AbSlot;
+use crate::editor::texture::TextureError;
+use crate::editor::ui::{ColorBox, ColorBoxPipeline, Label, StaticBoxPipeline};
+
+use boxi::prelude::*;
+use std::sync::Arc;
+
+/// Switches between the A and B states and copies the active one over the other.
+pub struct CompareBar {
+    a: Label<1>,
+    b: Label<1>,
+    pub copy: Label<3>,
+    /// Dims the inactive slot.
+    dim_a: ColorBox,
+    dim_b: ColorBox,
+    backdrop: ColorBox,
+    active: AbSlot,
+}
+
+impl CompareBar {
+    pub fn new(
+        device: &wgpu::Device,
+        position: (u16, u16),
+        font_pipeline: Arc<StaticBoxPipeline>,
+        color_pipeline: Arc<ColorBoxPipeline>,
+    ) -> Result<Self, TextureError> {
+        let (x, y) = position;
+        let dim = [0.0, 0.0, 0.0, 0.7];
+
+        let mut a = Label::new(device, (x + 2, y + 2), font_pipeline.clone())?;
+        a.change_text("A");
+
+        let mut b = Label::new(device, (x + 8, y + 2), font_pipeline.clone())?;
+        b.change_text("B");
+
+        Ok(Self {
+            a,
+            b,
+            copy: Label::new(device, (x + 28, y + 2), font_pipeline)?,
+            dim_a: ColorBox::new(device, (x + 2, y + 2), 4, 6, dim, color_pipeline.clone()),
+            dim_b: ColorBox::new(device, (x + 8, y + 2), 4, 6, dim, color_pipeline.clone()),
+            backdrop: ColorBox::new(
+                device,
+                position,
+                42,
+                10,
+                [0.0, 0.0, 0.0, 0.8],
+                color_pipeline,
+            ),
+            active: AbSlot::A,
+        })
+    }
+
+    /// The slot under the mouse, if any.
+    pub fn slot_at(&self, pos: (i16, i16)) -> Option<AbSlot> {
+        if self.a.is_mouse_over(pos) {
+            Some(AbSlot::A)
+        } else if self.b.is_mouse_over(pos) {
+            Some(AbSlot::B)
+        } else {
+            None
+        }
+    }
+}
+
+impl UiInteractive<BitFlipperParams> for CompareBar {}
+impl UiElement<BitFlipperParams> for CompareBar {
+    fn prerender(
+        &mut self,
+        queue: &wgpu::Queue,
+        params: Arc<crate::BitFlipperParams>,
+        buffer: &[f32],
+    ) {
+        self.active = params.editor_state.compare.active();
+
+        let copy = format!("{}>{}", self.active.name(), self.active.other().name());
+        self.copy.change_text(&copy);
+
+        self.a.prerender(queue, params.clone(), buffer);
+        self.b.prerender(queue, params.clone(), buffer);
+        self.copy.prerender(queue, params, buffer);
+    }
+
+    fn render(&self, render_pass: &mut wgpu::RenderPass) {
+        self.backdrop.render(render_pass);
+        self.a.render(render_pass);
+        self.b.render(render_pass);
+        self.copy.render(render_pass);
+
+        match self.active {
+            AbSlot::A => self.dim_b.render(render_pass),
+            AbSlot::B => self.dim_a.render(render_pass),
+        }
+    }
+}
+
+impl UiBox for CompareBar {
+    fn width(&self) -> u16 {
+        self.backdrop.width()
+    }
+
+    fn height(&self) -> u16 {
+        self.backdrop.height()
+    }
+
+    fn position(&self) -> (u16, u16) {
+        self.backdrop.position()
+    }
+}
@@ -1,4 +1,7 @@
-use super::{StaticBox, StaticBoxPipeline, texture::TextureError};
+use super::{
+    ColorBox, ColorBoxPipeline, FieldPanel, StaticBox, StaticBoxPipeline, Text,
+    texture::TextureError,
+};
 
 use crate::{
     BitFlipperParams,
@@ -50,6 +53,18 @@ impl Digit {
         self.id
     }
 
+    /// Lights the digit up if its bit is set in `mask`.
+    fn show(&mut self, queue: &wgpu::Queue, mask: u64) {
+        let val = (mask >> (self.id - 1)) & 1 == 1;
+
+        if self.is_on != val {
+            self.is_on = val;
+
+            let uv_id = self.random_uv(val.into());
+            self.static_box.swap_uv(queue, &uv_id).ok();
+        }
+    }
+
     fn random_uv(&self, d_type: DigitType) -> UVSegment {
         let n: u8 = fastrand::u8(..=8);
 
@@ -86,18 +101,7 @@ impl UiElement<BitFlipperParams> for Digit {
         params: Arc<crate::BitFlipperParams>,
         _buffer: &[f32],
     ) {
-        let val = params
-            .bits
-            .get_bit_param(self.id)
-            .map(|param| param.value())
-            .unwrap_or_default();
-
-        if self.is_on != val {
-            self.is_on = val;
-
-            let uv_id = self.random_uv(val.into());
-            self.static_box.swap_uv(queue, &uv_id).ok();
-        }
+        self.show(queue, params.mask_bits());
     }
 
     fn render(&self, render_pass: &mut wgpu::RenderPass) {
@@ -120,35 +124,138 @@ impl UiBox for Digit {
     }
 }
 
-pub struct DigitCluster {
-    pub digits: Vec<Digit>,
+/// Places the bits `high` down to `low` in rows of four digits, the first one at height `y`.
+fn rows(high: u8, low: u8, y: u16) -> impl Iterator<Item = (u8, (u16, u16))> {
+    (low..=high).rev().enumerate().map(move |(i, id)| {
+        let (column, row) = ((i % 4) as u16, (i / 4) as u16);
+
+        (id, (75 + column * 11, y + row * 11))
+    })
 }
 
-impl DigitCluster {
-    pub fn new(device: &wgpu::Device, pipeline: Arc<StaticBoxPipeline>) -> Self {
-        let mut digits = Vec::<_>::new();
+/// The digits of one page, placed so each row only holds bits of one field.
+struct DigitLayout {
+    digits: Vec<Digit>,
+    /// One per digit, darkens the digits outside the field selected in [`FieldPanel`].
+    field_dims: Vec<ColorBox>,
+    /// One per digit, lights up the digits the host is modulating.
+    modulation_marks: Vec<ColorBox>,
+    /// One per digit, underlines the digits the macros flip.
+    macro_marks: Vec<ColorBox>,
+}
+
+impl DigitLayout {
+    fn new(
+        device: &wgpu::Device,
+        bits: impl Iterator<Item = (u8, (u16, u16))>,
+        pipeline: Arc<StaticBoxPipeline>,
+        color_pipeline: Arc<ColorBoxPipeline>,
+    ) -> Self {
+        let digits: Vec<_> = bits
+            .map(|(id, position)| Digit::new(device, id, position, pipeline.clone()).unwrap())
+            .collect();
+
+        let field_dims = digits
+            .iter()
+            .map(|digit| {
+                let color = [0.0, 0.0, 0.0, 0.6];
+                ColorBox::new(device, digit.position, 9, 6, color, color_pipeline.clone())
+            })
+            .collect();
 
-        digits.push(Digit::new(device, 32, (75, 30), pipeline.clone()).unwrap());
+        let modulation_marks = digits
+            .iter()
+            .map(|digit| {
+                let color = [1.0, 1.0, 1.0, 0.3];
+                ColorBox::new(device, digit.position, 9, 6, color, color_pipeline.clone())
+            })
+            .collect();
 
-        for i in 0..8usize {
-            let x = 75 + (i & 3) * 11;
-            let y = 45 + (i / 4) * 11;
+        let macro_marks = digits
+            .iter()
+            .map(|digit| {
+                let (x, y) = digit.position;
+                let color = [1.0, 1.0, 1.0, 0.8];
+                ColorBox::new(device, (x, y + 7), 9, 1, color, color_pipeline.clone())
+            })
+            .collect();
 
-            digits.push(
-                Digit::new(device, 31 - i as u8, (x as u16, y as u16), pipeline.clone()).unwrap(),
-            );
+        Self {
+            digits,
+            field_dims,
+            modulation_marks,
+            macro_marks,
         }
+    }
+}
+
+pub struct DigitCluster {
+    /// Single precision with its 1/8/23 split, then the two pages of double precision: the sign,
+    /// the 11 exponent bits and the top of the mantissa, then the rest of the mantissa.
+    layouts: [DigitLayout; 3],
+    /// Which of the layouts is on screen.
+    layout: usize,
+    pub page_toggle: PageToggle,
+    pub fields: FieldPanel,
+    /// Darkens the digits while the plugin is bypassed.
+    dim: ColorBox,
+    /// The bits the host is modulating.
+    modulated: u64,
+    /// The bits the macros flip.
+    macro_changes: u64,
+    bypassed: bool,
+    double_precision: bool,
+}
+
+impl DigitCluster {
+    pub fn new(
+        device: &wgpu::Device,
+        pipeline: Arc<StaticBoxPipeline>,
+        font_pipeline: Arc<StaticBoxPipeline>,
+        color_pipeline: Arc<ColorBoxPipeline>,
+    ) -> Self {
+        let single = rows(32, 32, 30)
+            .chain(rows(31, 24, 45))
+            .chain(rows(23, 1, 71));
+        let upper = rows(64, 64, 30)
+            .chain(rows(63, 53, 45))
+            .chain(rows(52, 33, 82));
+        let lower = rows(32, 1, 45);
 
-        for i in 0..23usize {
-            let x = 75 + (i & 3) * 11;
-            let y = 71 + (i / 4) * 11;
+        let layouts = [
+            DigitLayout::new(device, single, pipeline.clone(), color_pipeline.clone()),
+            DigitLayout::new(device, upper, pipeline.clone(), color_pipeline.clone()),
+            DigitLayout::new(device, lower, pipeline.clone(), color_pipeline.clone()),
+        ];
 
-            digits.push(
-                Digit::new(device, 23 - i as u8, (x as u16, y as u16), pipeline.clone()).unwrap(),
-            );
+        let page_toggle = PageToggle::new(device, (111, 32), pipeline).unwrap();
+        let fields =
+            FieldPanel::new(device, (2, 66), font_pipeline, color_pipeline.clone()).unwrap();
+        let dim = ColorBox::new(
+            device,
+            (74, 29),
+            46,
+            109,
+            [0.0, 0.0, 0.0, 0.6],
+            color_pipeline,
+        );
+
+        Self {
+            layouts,
+            layout: 0,
+            page_toggle,
+            fields,
+            dim,
+            modulated: 0,
+            macro_changes: 0,
+            bypassed: false,
+            double_precision: false,
         }
+    }
 
-        Self { digits }
+    /// The digits on screen.
+    pub fn digits(&self) -> &[Digit] {
+        &self.layouts[self.layout].digits
     }
 }
 
@@ -160,15 +267,62 @@ impl UiElement<BitFlipperParams> for DigitCluster {
         params: Arc<crate::BitFlipperParams>,
         _buffer: &[f32],
     ) {
-        for digi in self.digits.iter_mut() {
-            digi.prerender(queue, params.clone(), _buffer);
+        self.page_toggle.prerender(queue, params.clone(), _buffer);
+        self.fields.prerender(queue, params.clone(), _buffer);
+        self.bypassed = params.bypass.value();
+        self.double_precision = params.double_precision.value();
+        self.modulated = params.bits.modulated();
+
+        self.layout = match self.double_precision {
+            true => 1 + self.page_toggle.page as usize,
+            false => 0,
+        };
+
+        // Shows the bits a click toggles, the ones the macros flip on top of those get underlined
+        let mask = params.mask_bits();
+        self.macro_changes = mask ^ params.mask();
+
+        for digi in self.layouts[self.layout].digits.iter_mut() {
+            digi.show(queue, mask);
         }
     }
 
     fn render(&self, render_pass: &mut wgpu::RenderPass) {
-        for digi in self.digits.iter() {
+        self.page_toggle.render(render_pass);
+
+        self.fields.render(render_pass);
+
+        let layout = &self.layouts[self.layout];
+
+        for digi in layout.digits.iter() {
             digi.render(render_pass);
         }
+
+        for (digi, mark) in layout.digits.iter().zip(&layout.modulation_marks) {
+            if (self.modulated >> (digi.id() - 1)) & 1 == 1 {
+                mark.render(render_pass);
+            }
+        }
+
+        for (digi, mark) in layout.digits.iter().zip(&layout.macro_marks) {
+            if (self.macro_changes >> (digi.id() - 1)) & 1 == 1 {
+                mark.render(render_pass);
+            }
+        }
+
+        if let Some(field) = self.fields.selected() {
+            let bits = field.bits(self.double_precision);
+
+            for (digi, dim) in layout.digits.iter().zip(&layout.field_dims) {
+                if !bits.contains(&digi.id()) {
+                    dim.render(render_pass);
+                }
+            }
+        }
+
+        if self.bypassed {
+            self.dim.render(render_pass);
+        }
     }
 }
 
@@ -183,3 +337,73 @@ impl UiBox for DigitCluster {
         (0, 0)
     }
 }
+
+/// Switches the [`DigitCluster`] between the upper and the lower 32 bits of the 64-bit mask.
+/// Only shown in double precision.
+pub struct PageToggle {
+    text: Text<1>,
+    position: (u16, u16),
+    page: u8,
+    visible: bool,
+}
+
+impl PageToggle {
+    fn new(
+        device: &wgpu::Device,
+        position: (u16, u16),
+        pipeline: Arc<StaticBoxPipeline>,
+    ) -> Result<Self, TextureError> {
+        Ok(Self {
+            text: Text::new(device, position, pipeline)?,
+            position,
+            page: 0,
+            visible: false,
+        })
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    pub fn next_page(&mut self) {
+        self.page = (self.page + 1) % 2;
+    }
+}
+
+impl UiInteractive<BitFlipperParams> for PageToggle {}
+impl UiElement<BitFlipperParams> for PageToggle {
+    fn prerender(
+        &mut self,
+        queue: &wgpu::Queue,
+        params: Arc<crate::BitFlipperParams>,
+        buffer: &[f32],
+    ) {
+        self.visible = params.double_precision.value();
+
+        let text = match self.visible {
+            true => (self.page + 1).to_string(),
+            false => String::new(),
+        };
+
+        self.text.change_text(&text);
+        self.text.prerender(queue, params, buffer);
+    }
+
+    fn render(&self, render_pass: &mut wgpu::RenderPass) {
+        self.text.render(render_pass);
+    }
+}
+
+impl UiBox for PageToggle {
+    fn width(&self) -> u16 {
+        6
+    }
+
+    fn height(&self) -> u16 {
+        8
+    }
+
+    fn position(&self) -> (u16, u16) {
+        self.position
+    }
+}
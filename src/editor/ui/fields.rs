@@ -0,0 +1,229 @@
+use crate::BitFlipperParams;
+use crate::editor::texture::TextureError;
+use crate::editor::ui::{ColorBox, ColorBoxPipeline, Label, StaticBoxPipeline};
+use crate::model::{FieldOp, FlipModes, FloatField};
+
+use boxi::prelude::*;
+use std::sync::Arc;
+
+/// Names the sign, exponent and mantissa of the mask, shows what the mask does to the selected
+/// field and edits that field as a whole.
+pub struct FieldPanel {
+    sign: Label<1>,
+    exponent: Label<3>,
+    mantissa: Label<3>,
+    /// How many of the field's bits are set.
+    count: Label<10>,
+    /// How far the field's bits can move a sample.
+    effect: Label<10>,
+    clear: Label<3>,
+    invert: Label<3>,
+    top: Label<3>,
+    fewer: Label<1>,
+    top_count: Label<2>,
+    more: Label<1>,
+    /// Dims the fields that aren't selected.
+    dims: [ColorBox; 3],
+    backdrop: ColorBox,
+    selected: Option<FloatField>,
+    /// How many bits the `TOP` operation sets.
+    top_n: u8,
+    double_precision: bool,
+}
+
+impl FieldPanel {
+    pub fn new(
+        device: &wgpu::Device,
+        position: (u16, u16),
+        font_pipeline: Arc<StaticBoxPipeline>,
+        color_pipeline: Arc<ColorBoxPipeline>,
+    ) -> Result<Self, TextureError> {
+        let (x, y) = position;
+        let dim = [0.0, 0.0, 0.0, 0.7];
+
+        Ok(Self {
+            sign: Label::new(device, (x + 2, y + 2), font_pipeline.clone())?.with_text("S"),
+            exponent: Label::new(device, (x + 10, y + 2), font_pipeline.clone())?.with_text("EXP"),
+            mantissa: Label::new(device, (x + 26, y + 2), font_pipeline.clone())?.with_text("MAN"),
+            count: Label::new(device, (x + 2, y + 10), font_pipeline.clone())?,
+            effect: Label::new(device, (x + 2, y + 17), font_pipeline.clone())?,
+            clear: Label::new(device, (x + 2, y + 25), font_pipeline.clone())?.with_text("CLR"),
+            invert: Label::new(device, (x + 18, y + 25), font_pipeline.clone())?.with_text("INV"),
+            top: Label::new(device, (x + 2, y + 32), font_pipeline.clone())?.with_text("TOP"),
+            fewer: Label::new(device, (x + 18, y + 32), font_pipeline.clone())?.with_text("-"),
+            top_count: Label::new(device, (x + 24, y + 32), font_pipeline.clone())?,
+            more: Label::new(device, (x + 34, y + 32), font_pipeline.clone())?.with_text("+"),
+            dims: [
+                ColorBox::new(device, (x + 2, y + 2), 4, 6, dim, color_pipeline.clone()),
+                ColorBox::new(device, (x + 10, y + 2), 12, 6, dim, color_pipeline.clone()),
+                ColorBox::new(device, (x + 26, y + 2), 12, 6, dim, color_pipeline.clone()),
+            ],
+            backdrop: ColorBox::new(
+                device,
+                position,
+                42,
+                40,
+                [0.0, 0.0, 0.0, 0.8],
+                color_pipeline,
+            ),
+            selected: None,
+            top_n: 4,
+            double_precision: false,
+        })
+    }
+
+    pub fn selected(&self) -> Option<FloatField> {
+        self.selected
+    }
+
+    fn field_at(&self, pos: (i16, i16)) -> Option<FloatField> {
+        if self.sign.is_mouse_over(pos) {
+            Some(FloatField::Sign)
+        } else if self.exponent.is_mouse_over(pos) {
+            Some(FloatField::Exponent)
+        } else if self.mantissa.is_mouse_over(pos) {
+            Some(FloatField::Mantissa)
+        } else {
+            None
+        }
+    }
+
+    /// Selects a field, or returns the operation that was clicked.
+    pub fn click(&mut self, pos: (i16, i16)) -> Option<FieldOp> {
+        if let Some(field) = self.field_at(pos) {
+            // Clicking the selected field again shows all of them
+            self.selected = (self.selected != Some(field)).then_some(field);
+
+            return None;
+        }
+
+        let field = self.selected?;
+        let len = field.len(self.double_precision);
+
+        if self.clear.is_mouse_over(pos) {
+            Some(FieldOp::Clear(field))
+        } else if self.invert.is_mouse_over(pos) {
+            Some(FieldOp::Invert(field))
+        } else if self.top.is_mouse_over(pos) {
+            Some(FieldOp::SetTop(field, self.top_n.min(len)))
+        } else {
+            if self.fewer.is_mouse_over(pos) {
+                self.top_n = self.top_n.min(len).saturating_sub(1).max(1);
+            } else if self.more.is_mouse_over(pos) {
+                self.top_n = (self.top_n + 1).min(len);
+            }
+
+            None
+        }
+    }
+}
+
+/// What the field's bits in `mask` do to a sample, short enough to fit the panel. For the exponent
+/// and mantissa that's how far the bits that change can move it.
+fn effect(field: FloatField, mask: u64, mode: FlipModes, double_precision: bool) -> String {
+    let len = field.len(double_precision);
+    let bits = field.extract(mask, double_precision);
+
+    // AND changes the bits that are left out of the mask
+    let changed = match mode {
+        FlipModes::And => !bits & (u64::MAX >> (64 - len)),
+        _ => bits,
+    };
+
+    match (field, mode) {
+        (_, FlipModes::Not) => String::from("NO MASK"),
+        _ if changed == 0 => String::from("NO EFFECT"),
+        (FloatField::Sign, FlipModes::And) => String::from("POSITIVE"),
+        (FloatField::Sign, FlipModes::Or) => String::from("NEGATIVE"),
+        (FloatField::Sign, _) => String::from("FLIPS SIGN"),
+        (FloatField::Exponent, _) => match changed {
+            0..=20 => format!("X{}", 1u64 << changed),
+            _ => format!("X2^{changed}"),
+        },
+        (FloatField::Mantissa, _) => {
+            let percent = changed as f64 / (1u64 << len) as f64 * 100.0;
+
+            match percent < 0.01 {
+                true => String::from("+-<0.01%"),
+                false => format!("+-{percent:.2}%"),
+            }
+        }
+    }
+}
+
+impl UiInteractive<BitFlipperParams> for FieldPanel {}
+impl UiElement<BitFlipperParams> for FieldPanel {
+    fn prerender(
+        &mut self,
+        queue: &wgpu::Queue,
+        params: Arc<crate::BitFlipperParams>,
+        buffer: &[f32],
+    ) {
+        self.double_precision = params.double_precision.value();
+
+        if let Some(field) = self.selected {
+            // Same as the digits and the field operations, the mask before the macros
+            let mask = params.mask_bits();
+            let len = field.len(self.double_precision);
+            let set = field.extract(mask, self.double_precision).count_ones();
+
+            self.count.change_text(&format!("{set}/{len} BITS"));
+            self.effect
+                .change_text(&effect(field, mask, params.mode(), self.double_precision));
+            self.top_count
+                .change_text(&format!("{:>2}", self.top_n.min(len)));
+        }
+
+        self.sign.prerender(queue, params.clone(), buffer);
+        self.exponent.prerender(queue, params.clone(), buffer);
+        self.mantissa.prerender(queue, params.clone(), buffer);
+        self.count.prerender(queue, params.clone(), buffer);
+        self.effect.prerender(queue, params.clone(), buffer);
+        self.clear.prerender(queue, params.clone(), buffer);
+        self.invert.prerender(queue, params.clone(), buffer);
+        self.top.prerender(queue, params.clone(), buffer);
+        self.fewer.prerender(queue, params.clone(), buffer);
+        self.top_count.prerender(queue, params.clone(), buffer);
+        self.more.prerender(queue, params, buffer);
+    }
+
+    fn render(&self, render_pass: &mut wgpu::RenderPass) {
+        self.backdrop.render(render_pass);
+        self.sign.render(render_pass);
+        self.exponent.render(render_pass);
+        self.mantissa.render(render_pass);
+
+        let Some(selected) = self.selected else {
+            return;
+        };
+
+        for (field, dim) in FloatField::ALL.iter().zip(&self.dims) {
+            if *field != selected {
+                dim.render(render_pass);
+            }
+        }
+
+        self.count.render(render_pass);
+        self.effect.render(render_pass);
+        self.clear.render(render_pass);
+        self.invert.render(render_pass);
+        self.top.render(render_pass);
+        self.fewer.render(render_pass);
+        self.top_count.render(render_pass);
+        self.more.render(render_pass);
+    }
+}
+
+impl UiBox for FieldPanel {
+    fn width(&self) -> u16 {
+        self.backdrop.width()
+    }
+
+    fn height(&self) -> u16 {
+        self.backdrop.height()
+    }
+
+    fn position(&self) -> (u16, u16) {
+        self.backdrop.position()
+    }
+}
@@ -0,0 +1,118 @@
+//! A tiny 3x5 pixel font for free-form text like preset names. The texture atlas only has the
+//! glyphs needed for numbers, so the font is rendered into an atlas of its own when the editor
+//! opens. Being white on transparent, it follows the palette like everything else.
+
+use super::texture::{TextureAtlas, UVSegment};
+
+use std::collections::HashMap;
+
+pub const GLYPH_WIDTH: u16 = 4;
+pub const GLYPH_HEIGHT: u16 = 6;
+
+/// Every row is 3 pixels wide, the highest bit being the leftmost pixel.
+#[rustfmt::skip]
+const GLYPHS: &[(char, [u8; 5])] = &[
+    (' ', [0b000, 0b000, 0b000, 0b000, 0b000]),
+    ('A', [0b010, 0b101, 0b111, 0b101, 0b101]),
+    ('B', [0b110, 0b101, 0b110, 0b101, 0b110]),
+    ('C', [0b011, 0b100, 0b100, 0b100, 0b011]),
+    ('D', [0b110, 0b101, 0b101, 0b101, 0b110]),
+    ('E', [0b111, 0b100, 0b110, 0b100, 0b111]),
+    ('F', [0b111, 0b100, 0b110, 0b100, 0b100]),
+    ('G', [0b011, 0b100, 0b101, 0b101, 0b011]),
+    ('H', [0b101, 0b101, 0b111, 0b101, 0b101]),
+    ('I', [0b111, 0b010, 0b010, 0b010, 0b111]),
+    ('J', [0b001, 0b001, 0b001, 0b101, 0b010]),
+    ('K', [0b101, 0b101, 0b110, 0b101, 0b101]),
+    ('L', [0b100, 0b100, 0b100, 0b100, 0b111]),
+    ('M', [0b101, 0b111, 0b111, 0b101, 0b101]),
+    ('N', [0b110, 0b101, 0b101, 0b101, 0b101]),
+    ('O', [0b010, 0b101, 0b101, 0b101, 0b010]),
+    ('P', [0b110, 0b101, 0b110, 0b100, 0b100]),
+    ('Q', [0b010, 0b101, 0b101, 0b110, 0b011]),
+    ('R', [0b110, 0b101, 0b110, 0b101, 0b101]),
+    ('S', [0b011, 0b100, 0b010, 0b001, 0b110]),
+    ('T', [0b111, 0b010, 0b010, 0b010, 0b010]),
+    ('U', [0b101, 0b101, 0b101, 0b101, 0b111]),
+    ('V', [0b101, 0b101, 0b101, 0b101, 0b010]),
+    ('W', [0b101, 0b101, 0b111, 0b111, 0b101]),
+    ('X', [0b101, 0b101, 0b010, 0b101, 0b101]),
+    ('Y', [0b101, 0b101, 0b010, 0b010, 0b010]),
+    ('Z', [0b111, 0b001, 0b010, 0b100, 0b111]),
+    ('0', [0b111, 0b101, 0b101, 0b101, 0b111]),
+    ('1', [0b010, 0b110, 0b010, 0b010, 0b111]),
+    ('2', [0b110, 0b001, 0b010, 0b100, 0b111]),
+    ('3', [0b110, 0b001, 0b010, 0b001, 0b110]),
+    ('4', [0b101, 0b101, 0b111, 0b001, 0b001]),
+    ('5', [0b111, 0b100, 0b110, 0b001, 0b110]),
+    ('6', [0b011, 0b100, 0b111, 0b101, 0b111]),
+    ('7', [0b111, 0b001, 0b010, 0b010, 0b010]),
+    ('8', [0b111, 0b101, 0b111, 0b101, 0b111]),
+    ('9', [0b111, 0b101, 0b111, 0b001, 0b110]),
+    ('-', [0b000, 0b000, 0b111, 0b000, 0b000]),
+    ('_', [0b000, 0b000, 0b000, 0b000, 0b111]),
+    ('.', [0b000, 0b000, 0b000, 0b000, 0b010]),
+    (':', [0b000, 0b010, 0b000, 0b010, 0b000]),
+    ('+', [0b000, 0b010, 0b111, 0b010, 0b000]),
+    ('*', [0b000, 0b101, 0b010, 0b101, 0b000]),
+    ('%', [0b101, 0b001, 0b010, 0b100, 0b101]),
+    ('/', [0b001, 0b001, 0b010, 0b100, 0b100]),
+    ('<', [0b001, 0b010, 0b100, 0b010, 0b001]),
+    ('>', [0b100, 0b010, 0b001, 0b010, 0b100]),
+    ('(', [0b001, 0b010, 0b010, 0b010, 0b001]),
+    (')', [0b100, 0b010, 0b010, 0b010, 0b100]),
+    ('[', [0b011, 0b010, 0b010, 0b010, 0b011]),
+    (']', [0b110, 0b010, 0b010, 0b010, 0b110]),
+    ('!', [0b010, 0b010, 0b010, 0b000, 0b010]),
+    ('?', [0b110, 0b001, 0b010, 0b000, 0b010]),
+    ('&', [0b010, 0b101, 0b010, 0b101, 0b011]),
+    ('|', [0b010, 0b010, 0b010, 0b010, 0b010]),
+    ('^', [0b010, 0b101, 0b000, 0b000, 0b000]),
+    ('\'', [0b010, 0b010, 0b000, 0b000, 0b000]),
+];
+
+/// The glyph used for a character. The font only has capitals, characters without a glyph
+/// show up as `?`.
+pub fn glyph(ch: char) -> char {
+    let ch = ch.to_ascii_uppercase();
+
+    match is_supported(ch) {
+        true => ch,
+        false => '?',
+    }
+}
+
+pub fn is_supported(ch: char) -> bool {
+    let ch = ch.to_ascii_uppercase();
+
+    GLYPHS.iter().any(|(glyph, _)| *glyph == ch)
+}
+
+/// Renders every glyph next to each other, each in a cell with a pixel of spacing.
+pub fn font_atlas(device: &wgpu::Device, queue: &wgpu::Queue) -> TextureAtlas {
+    let width = GLYPHS.len() as u32 * GLYPH_WIDTH as u32;
+
+    let img = image::RgbaImage::from_fn(width, GLYPH_HEIGHT as u32, |x, y| {
+        let (_, rows) = GLYPHS[(x / GLYPH_WIDTH as u32) as usize];
+        let column = x % GLYPH_WIDTH as u32;
+
+        let lit = column < 3 && y < 5 && (rows[y as usize] >> (2 - column)) & 1 == 1;
+
+        match lit {
+            true => image::Rgba([255, 255, 255, 255]),
+            false => image::Rgba([0, 0, 0, 0]),
+        }
+    });
+
+    let bounds_map: HashMap<_, _> = GLYPHS
+        .iter()
+        .enumerate()
+        .map(|(i, (ch, _))| {
+            let x = i as u16 * GLYPH_WIDTH;
+
+            (UVSegment::UV_glyph(*ch), [x, 0, GLYPH_WIDTH, GLYPH_HEIGHT])
+        })
+        .collect();
+
+    TextureAtlas::from_image(device, queue, &img, bounds_map)
+}
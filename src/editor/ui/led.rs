@@ -0,0 +1,70 @@
+use crate::BitFlipperParams;
+use crate::bus::{Bus, Meters};
+use crate::editor::ui::{ColorBox, ColorBoxPipeline};
+
+use boxi::prelude::*;
+use std::sync::Arc;
+
+const LED_SIZE: u16 = 5;
+const COLOR_ON: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
+const COLOR_OFF: [f32; 4] = [0.15, 0.15, 0.15, 1.0];
+
+/// A small indicator lighting up while a meter reported by the audio thread is active.
+pub struct Led {
+    color_box: ColorBox,
+    bus: Arc<Bus>,
+    probe: fn(&Meters) -> bool,
+    is_on: bool,
+}
+
+impl Led {
+    pub fn new(
+        device: &wgpu::Device,
+        position: (u16, u16),
+        pipeline: Arc<ColorBoxPipeline>,
+        bus: Arc<Bus>,
+        probe: fn(&Meters) -> bool,
+    ) -> Self {
+        Self {
+            color_box: ColorBox::new(device, position, LED_SIZE, LED_SIZE, COLOR_OFF, pipeline),
+            bus,
+            probe,
+            is_on: false,
+        }
+    }
+}
+
+impl UiElement<BitFlipperParams> for Led {
+    fn prerender(
+        &mut self,
+        queue: &wgpu::Queue,
+        _params: Arc<crate::BitFlipperParams>,
+        _buffer: &[f32],
+    ) {
+        let val = (self.probe)(&self.bus.meters);
+
+        if self.is_on != val {
+            self.is_on = val;
+            self.color_box
+                .set_color(queue, if val { COLOR_ON } else { COLOR_OFF });
+        }
+    }
+
+    fn render(&self, render_pass: &mut wgpu::RenderPass) {
+        self.color_box.render(render_pass);
+    }
+}
+
+impl UiBox for Led {
+    fn width(&self) -> u16 {
+        LED_SIZE
+    }
+
+    fn height(&self) -> u16 {
+        LED_SIZE
+    }
+
+    fn position(&self) -> (u16, u16) {
+        self.color_box.position()
+    }
+}
@@ -186,12 +186,7 @@ impl UiElement<BitFlipperParams> for MonitorGroup {
         let resampled = resample_into::<{ MONITOR_WIDTH as usize }>(buffer);
         let mut remapped = sine_wave::<{ MONITOR_WIDTH as usize }>();
 
-        let mask = params.bits.to_u32();
-        let mode = params.mode.value();
-
-        for sample in remapped.iter_mut() {
-            *sample = mode.transform(*sample, mask);
-        }
+        params.settings().transform().apply_slice(&mut remapped);
 
         self.monitor_1.custom_prerender(queue, &resampled);
         self.monitor_2.custom_prerender(queue, &remapped);
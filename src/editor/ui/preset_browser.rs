@@ -0,0 +1,595 @@
+use crate::BitFlipperParams;
+use crate::editor::texture::TextureError;
+use crate::editor::ui::font::GLYPH_WIDTH;
+use crate::editor::ui::{ColorBox, ColorBoxPipeline, Label, StaticBoxPipeline};
+use crate::editor::{VIEW_HEIGHT, VIEW_WIDTH};
+use crate::preset::{self, Preset, PresetError, USER_CATEGORY};
+
+use boxi::prelude::*;
+use keyboard_types::{Key, KeyState, KeyboardEvent};
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+
+/// Characters of the preset name shown in the bar.
+const NAME_LEN: usize = 7;
+/// Longest name that can be typed when saving a preset.
+const MAX_NAME_LEN: usize = 24;
+
+const LIST_POSITION: (u16, u16) = (40, 24);
+const LIST_ROWS: usize = 20;
+const ROW_LEN: usize = 30;
+const ROW_HEIGHT: u16 = 8;
+/// Lines of the reason shown below the bar when loading or saving failed.
+const ERROR_LINES: usize = 2;
+
+/// What the editor needs to do after the browser handled an event.
+pub enum BrowserResponse {
+    /// The event wasn't meant for the browser, the host may want to handle it.
+    Ignored,
+    Handled,
+    /// The user picked a preset that needs to be applied to the parameters.
+    Load(Preset),
+}
+
+enum BrowserMode {
+    Closed,
+    /// The list overlay is open, with the index of the highlighted preset.
+    List(usize),
+    /// A name for a new preset is being typed.
+    Naming(String),
+    /// A preset with the typed name exists already. Saving again replaces it, anything else
+    /// goes back to typing.
+    Replace(String),
+    /// Loading or saving didn't work out, with the reason. Shown until the next click or key.
+    Failed(String),
+}
+
+enum Row {
+    Category(String),
+    Preset(usize),
+}
+
+/// Browses the presets on disk. A bar in the corner steps through them and shows whether the
+/// parameters have been changed since, the list opens as an overlay over the whole editor.
+pub struct PresetBrowser {
+    backdrop: ColorBox,
+    prev: Label<1>,
+    name: Label<NAME_LEN>,
+    next: Label<1>,
+    list_btn: Label<4>,
+    save_btn: Label<4>,
+    dirty: Label<1>,
+
+    error_backdrop: ColorBox,
+    error: [Label<ROW_LEN>; ERROR_LINES],
+
+    overlay: ColorBox,
+    title: Label<ROW_LEN>,
+    rows: Vec<Label<ROW_LEN>>,
+
+    presets: Vec<Preset>,
+    /// The preset that was loaded or saved last.
+    current: Option<usize>,
+    mode: BrowserMode,
+    /// The first row of the list that is visible.
+    scroll: usize,
+    is_dirty: bool,
+    /// Whether the current preset changed since `is_dirty` was last worked out. Changed
+    /// parameters are flagged by the host.
+    needs_check: bool,
+}
+
+impl PresetBrowser {
+    pub fn new(
+        device: &wgpu::Device,
+        position: (u16, u16),
+        font_pipeline: Arc<StaticBoxPipeline>,
+        color_pipeline: Arc<ColorBoxPipeline>,
+    ) -> Result<Self, TextureError> {
+        let (x, y) = position;
+        let rows: Vec<Label<ROW_LEN>> = (0..LIST_ROWS)
+            .map(|i| {
+                let row_y = LIST_POSITION.1 + i as u16 * ROW_HEIGHT;
+
+                Label::new(device, (LIST_POSITION.0, row_y), font_pipeline.clone())
+            })
+            .collect::<Result<_, _>>()?;
+
+        let mut title = Label::new(device, (LIST_POSITION.0, 10), font_pipeline.clone())?;
+        title.change_text("Presets");
+
+        let mut list_btn = Label::new(device, (x + 2, y + 10), font_pipeline.clone())?;
+        list_btn.change_text("List");
+
+        let mut save_btn = Label::new(device, (x + 20, y + 10), font_pipeline.clone())?;
+        save_btn.change_text("Save");
+
+        let mut prev = Label::new(device, (x + 2, y + 2), font_pipeline.clone())?;
+        prev.change_text("<");
+
+        let mut next = Label::new(device, (x + 37, y + 2), font_pipeline.clone())?;
+        next.change_text(">");
+
+        let error = std::array::try_from_fn(|i| {
+            let line_y = y + 20 + i as u16 * ROW_HEIGHT;
+
+            Label::new(device, (x + 2, line_y), font_pipeline.clone())
+        })?;
+
+        Ok(Self {
+            backdrop: ColorBox::new(
+                device,
+                position,
+                42,
+                18,
+                [0.0, 0.0, 0.0, 0.8],
+                color_pipeline.clone(),
+            ),
+            prev,
+            name: Label::new(device, (x + 7, y + 2), font_pipeline.clone())?,
+            next,
+            list_btn,
+            save_btn,
+            dirty: Label::new(device, (x + 37, y + 10), font_pipeline)?,
+
+            error_backdrop: ColorBox::new(
+                device,
+                (x, y + 18),
+                ROW_LEN as u16 * GLYPH_WIDTH + 4,
+                ERROR_LINES as u16 * ROW_HEIGHT + 2,
+                [0.0, 0.0, 0.0, 0.8],
+                color_pipeline.clone(),
+            ),
+            error,
+
+            overlay: ColorBox::new(
+                device,
+                (0, 0),
+                VIEW_WIDTH,
+                VIEW_HEIGHT,
+                [0.0, 0.0, 0.0, 0.9],
+                color_pipeline,
+            ),
+            title,
+            rows,
+
+            presets: preset::list().into_iter().map(|(_, p)| p).collect(),
+            current: None,
+            mode: BrowserMode::Closed,
+            scroll: 0,
+            is_dirty: false,
+            needs_check: true,
+        })
+    }
+
+    /// Whether the list overlay covers the editor.
+    pub fn is_open(&self) -> bool {
+        matches!(self.mode, BrowserMode::List(_))
+    }
+
+    fn reload(&mut self) {
+        self.presets = preset::list().into_iter().map(|(_, p)| p).collect();
+        self.current = None;
+        self.needs_check = true;
+    }
+
+    /// Shows why a preset couldn't be loaded or saved.
+    pub fn fail(&mut self, err: &PresetError) {
+        self.mode = BrowserMode::Failed(err.to_string());
+    }
+
+    fn open_list(&mut self) {
+        let current = self.current.map(|i| self.presets[i].clone());
+        self.reload();
+
+        // Find the current preset again, the files might have changed in the meantime
+        self.current = current.and_then(|current| {
+            self.presets
+                .iter()
+                .position(|p| p.name == current.name && p.category == current.category)
+        });
+
+        self.select(self.current.unwrap_or(0));
+    }
+
+    fn select(&mut self, index: usize) {
+        self.mode = BrowserMode::List(index);
+
+        let Some(row) = self
+            .rows()
+            .iter()
+            .position(|row| matches!(row, Row::Preset(i) if *i == index))
+        else {
+            return;
+        };
+
+        // Keep the selection in view, along with the category above it
+        if row < self.scroll + 1 {
+            self.scroll = row.saturating_sub(1);
+        } else if row >= self.scroll + LIST_ROWS {
+            self.scroll = row + 1 - LIST_ROWS;
+        }
+    }
+
+    fn load(&mut self, index: usize) -> BrowserResponse {
+        self.mode = BrowserMode::Closed;
+
+        match self.presets.get(index) {
+            Some(preset) => {
+                self.current = Some(index);
+                self.needs_check = true;
+                BrowserResponse::Load(preset.clone())
+            }
+            None => BrowserResponse::Handled,
+        }
+    }
+
+    /// Steps through the presets, wrapping around at both ends.
+    fn step(&mut self, forward: bool) -> BrowserResponse {
+        let len = self.presets.len();
+        if len == 0 {
+            return BrowserResponse::Handled;
+        }
+
+        let index = match (self.current, forward) {
+            (Some(i), true) => (i + 1) % len,
+            (Some(i), false) => (i + len - 1) % len,
+            (None, true) => 0,
+            (None, false) => len - 1,
+        };
+
+        self.load(index)
+    }
+
+    fn save(&mut self, params: &BitFlipperParams, name: &str, overwrite: bool) {
+        self.mode = BrowserMode::Closed;
+
+        let name = name.trim();
+        if name.is_empty() {
+            return;
+        }
+
+        match preset::save(&preset::capture(params, name), overwrite) {
+            Ok(_) => {}
+            Err(PresetError::AlreadyExists(_)) => {
+                self.mode = BrowserMode::Replace(name.to_string());
+                return;
+            }
+            Err(err) => {
+                self.fail(&err);
+                return;
+            }
+        }
+
+        self.reload();
+        self.current = self
+            .presets
+            .iter()
+            .position(|p| p.name == name && p.category == USER_CATEGORY);
+    }
+
+    /// The list, with a header above every category.
+    fn rows(&self) -> Vec<Row> {
+        let mut rows = Vec::new();
+
+        for (i, preset) in self.presets.iter().enumerate() {
+            let is_new_category = match i.checked_sub(1) {
+                Some(prev) => self.presets[prev].category != preset.category,
+                None => true,
+            };
+
+            if is_new_category {
+                rows.push(Row::Category(preset.category.clone()));
+            }
+
+            rows.push(Row::Preset(i));
+        }
+
+        rows
+    }
+
+    pub fn click(&mut self, pos: (i16, i16), params: &BitFlipperParams) -> BrowserResponse {
+        match &self.mode {
+            BrowserMode::List(_) => {
+                let (list_x, list_y) = (LIST_POSITION.0 as i16, LIST_POSITION.1 as i16);
+                let list_width = ROW_LEN as i16 * GLYPH_WIDTH as i16;
+
+                let row = (pos.1 - list_y).max(0) as usize / ROW_HEIGHT as usize;
+                let rows = self.rows();
+
+                let clicked = match pos.0 >= list_x && pos.0 < list_x + list_width {
+                    true if pos.1 >= list_y && row < LIST_ROWS => rows.get(self.scroll + row),
+                    _ => None,
+                };
+
+                // Clicking anywhere outside the list closes it
+                match clicked {
+                    Some(Row::Preset(i)) => self.load(*i),
+                    Some(Row::Category(_)) => BrowserResponse::Handled,
+                    None => {
+                        self.mode = BrowserMode::Closed;
+                        BrowserResponse::Handled
+                    }
+                }
+            }
+            BrowserMode::Naming(name) if self.save_btn.is_mouse_over(pos) => {
+                let name = name.clone();
+                self.save(params, &name, false);
+
+                BrowserResponse::Handled
+            }
+            BrowserMode::Replace(name) => {
+                let name = name.clone();
+
+                match self.save_btn.is_mouse_over(pos) {
+                    true => self.save(params, &name, true),
+                    false => self.mode = BrowserMode::Naming(name),
+                }
+
+                BrowserResponse::Handled
+            }
+            _ => {
+                if matches!(self.mode, BrowserMode::Failed(_)) {
+                    self.mode = BrowserMode::Closed;
+                }
+
+                if self.prev.is_mouse_over(pos) {
+                    self.step(false)
+                } else if self.next.is_mouse_over(pos) {
+                    self.step(true)
+                } else if self.name.is_mouse_over(pos) || self.list_btn.is_mouse_over(pos) {
+                    self.open_list();
+                    BrowserResponse::Handled
+                } else if self.save_btn.is_mouse_over(pos) {
+                    let name = self.current.map(|i| self.presets[i].name.clone());
+                    self.mode = BrowserMode::Naming(name.unwrap_or_default());
+
+                    BrowserResponse::Handled
+                } else {
+                    BrowserResponse::Ignored
+                }
+            }
+        }
+    }
+
+    /// Scrolls the list by whole rows.
+    pub fn scroll(&mut self, rows: i32) {
+        let max = self.rows().len().saturating_sub(LIST_ROWS);
+
+        self.scroll = self.scroll.saturating_add_signed(rows as isize).min(max);
+    }
+
+    pub fn key(&mut self, event: &KeyboardEvent, params: &BitFlipperParams) -> BrowserResponse {
+        let is_down = event.state == KeyState::Down;
+
+        match &mut self.mode {
+            BrowserMode::Closed => BrowserResponse::Ignored,
+            BrowserMode::Failed(_) => {
+                self.mode = BrowserMode::Closed;
+                BrowserResponse::Ignored
+            }
+            BrowserMode::Replace(name) => {
+                if is_down {
+                    let name = name.clone();
+
+                    match event.key {
+                        Key::Enter => self.save(params, &name, true),
+                        _ => self.mode = BrowserMode::Naming(name),
+                    }
+                }
+
+                BrowserResponse::Handled
+            }
+            BrowserMode::List(selected) => {
+                let selected = *selected;
+
+                match (&event.key, is_down) {
+                    (Key::ArrowUp, true) => {
+                        self.select(selected.saturating_sub(1));
+                        BrowserResponse::Handled
+                    }
+                    (Key::ArrowDown, true) => {
+                        self.select((selected + 1).min(self.presets.len().saturating_sub(1)));
+                        BrowserResponse::Handled
+                    }
+                    (Key::Enter, true) => self.load(selected),
+                    (Key::Escape, true) => {
+                        self.mode = BrowserMode::Closed;
+                        BrowserResponse::Handled
+                    }
+                    (Key::ArrowUp | Key::ArrowDown | Key::Enter | Key::Escape, false) => {
+                        BrowserResponse::Handled
+                    }
+                    _ => BrowserResponse::Ignored,
+                }
+            }
+            // Everything typed goes to the name, it shouldn't trigger the host's shortcuts
+            BrowserMode::Naming(name) => {
+                if !is_down {
+                    return BrowserResponse::Handled;
+                }
+
+                match &event.key {
+                    Key::Character(text) => {
+                        let allowed = text
+                            .chars()
+                            .filter(|ch| ch.is_ascii_alphanumeric() || " -_".contains(*ch));
+
+                        name.extend(allowed);
+                        name.truncate(MAX_NAME_LEN);
+                    }
+                    Key::Backspace => {
+                        name.pop();
+                    }
+                    Key::Enter => {
+                        let name = name.clone();
+                        self.save(params, &name, false);
+                    }
+                    Key::Escape => self.mode = BrowserMode::Closed,
+                    _ => {}
+                }
+
+                BrowserResponse::Handled
+            }
+        }
+    }
+}
+
+/// Breaks `text` into lines of at most `len` characters, between words where possible.
+fn wrap(text: &str, len: usize) -> impl Iterator<Item = String> {
+    let mut rest: Vec<char> = text.chars().collect();
+
+    std::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+
+        let end = match rest.len() > len {
+            true => rest[..=len]
+                .iter()
+                .rposition(|ch| *ch == ' ')
+                .filter(|space| *space > 0)
+                .unwrap_or(len),
+            false => rest.len(),
+        };
+
+        let line: String = rest.drain(..end).collect();
+        while rest.first() == Some(&' ') {
+            rest.remove(0);
+        }
+
+        Some(line)
+    })
+}
+
+impl UiInteractive<BitFlipperParams> for PresetBrowser {}
+impl UiElement<BitFlipperParams> for PresetBrowser {
+    fn prerender(
+        &mut self,
+        queue: &wgpu::Queue,
+        params: Arc<crate::BitFlipperParams>,
+        buffer: &[f32],
+    ) {
+        let current = self.current.and_then(|i| self.presets.get(i));
+
+        // Comparing walks every parameter, so only do that once something changed
+        let params_changed = params
+            .editor_state
+            .params_changed
+            .swap(false, Ordering::Relaxed);
+
+        if params_changed || self.needs_check {
+            self.is_dirty = current.is_some_and(|p| !preset::matches(p, &params));
+            self.needs_check = false;
+        }
+
+        let name = match &self.mode {
+            // Shows the end of the name with a cursor behind it
+            BrowserMode::Naming(name) => {
+                let skip = (name.len() + 1).saturating_sub(NAME_LEN);
+                format!("{}_", &name[skip..])
+            }
+            BrowserMode::Replace(_) => "Replace".to_string(),
+            BrowserMode::Failed(_) => "Failed".to_string(),
+            _ => current.map_or_else(|| "---".to_string(), |preset| preset.name.clone()),
+        };
+
+        self.name.change_text(&name);
+        let marker = match self.mode {
+            BrowserMode::Replace(_) => "?",
+            _ if self.is_dirty => "*",
+            _ => "",
+        };
+        self.dirty.change_text(marker);
+
+        for label in [&mut self.prev, &mut self.next, &mut self.dirty] {
+            label.prerender(queue, params.clone(), buffer);
+        }
+
+        for label in [&mut self.list_btn, &mut self.save_btn] {
+            label.prerender(queue, params.clone(), buffer);
+        }
+
+        self.name.prerender(queue, params.clone(), buffer);
+
+        if let BrowserMode::Failed(reason) = &self.mode {
+            let lines = wrap(reason, ROW_LEN).chain(std::iter::repeat(String::new()));
+
+            for (label, line) in self.error.iter_mut().zip(lines) {
+                label.change_text(&line);
+                label.prerender(queue, params.clone(), buffer);
+            }
+        }
+
+        let BrowserMode::List(selected) = self.mode else {
+            return;
+        };
+
+        let rows = self.rows();
+        let visible = rows
+            .iter()
+            .skip(self.scroll)
+            .map(Some)
+            .chain(std::iter::repeat(None));
+
+        for (label, row) in self.rows.iter_mut().zip(visible) {
+            let text = match row {
+                Some(Row::Category(category)) => format!("{category}:"),
+                Some(Row::Preset(i)) => {
+                    let marker = if *i == selected { '>' } else { ' ' };
+                    format!(" {marker} {}", self.presets[*i].name)
+                }
+                None => String::new(),
+            };
+
+            label.change_text(&text);
+        }
+
+        self.title.prerender(queue, params.clone(), buffer);
+
+        for label in self.rows.iter_mut() {
+            label.prerender(queue, params.clone(), buffer);
+        }
+    }
+
+    fn render(&self, render_pass: &mut wgpu::RenderPass) {
+        self.backdrop.render(render_pass);
+        self.prev.render(render_pass);
+        self.name.render(render_pass);
+        self.next.render(render_pass);
+        self.list_btn.render(render_pass);
+        self.save_btn.render(render_pass);
+        self.dirty.render(render_pass);
+
+        if matches!(self.mode, BrowserMode::Failed(_)) {
+            self.error_backdrop.render(render_pass);
+
+            for label in self.error.iter() {
+                label.render(render_pass);
+            }
+        }
+
+        if self.is_open() {
+            self.overlay.render(render_pass);
+            self.title.render(render_pass);
+
+            for label in self.rows.iter() {
+                label.render(render_pass);
+            }
+        }
+    }
+}
+
+impl UiBox for PresetBrowser {
+    fn width(&self) -> u16 {
+        self.backdrop.width()
+    }
+
+    fn height(&self) -> u16 {
+        self.backdrop.height()
+    }
+
+    fn position(&self) -> (u16, u16) {
+        self.backdrop.position()
+    }
+}
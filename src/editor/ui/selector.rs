@@ -0,0 +1,77 @@
+use super::{Label, ParamGetter, StaticBoxPipeline, texture::TextureError};
+
+use crate::BitFlipperParams;
+
+use boxi::prelude::*;
+use nih_plug::prelude::*;
+use std::sync::Arc;
+
+/// Shows the first letters of an [`EnumParam`]'s value. Clicking it moves on to the next one.
+pub struct Selector<T: Enum + PartialEq + 'static> {
+    label: Label<3>,
+    param: ParamGetter<EnumParam<T>>,
+    index: Option<usize>,
+}
+
+impl<T: Enum + PartialEq + 'static> Selector<T> {
+    /// Needs a pipeline made with the font atlas.
+    pub fn new(
+        device: &wgpu::Device,
+        position: (u16, u16),
+        font_pipeline: Arc<StaticBoxPipeline>,
+        param: ParamGetter<EnumParam<T>>,
+    ) -> Result<Self, TextureError> {
+        Ok(Self {
+            label: Label::new(device, position, font_pipeline)?,
+            param,
+            index: None,
+        })
+    }
+
+    /// Sets the parameter to the value after the current one, wrapping around after the last.
+    pub fn next(&self, params: &BitFlipperParams, setter: &ParamSetter) {
+        let param = (self.param)(params);
+        let next = (param.value().to_index() + 1) % T::variants().len();
+
+        setter.begin_set_parameter(param);
+        setter.set_parameter(param, T::from_index(next));
+        setter.end_set_parameter(param);
+    }
+}
+
+impl<T: Enum + PartialEq + 'static> UiInteractive<BitFlipperParams> for Selector<T> {}
+impl<T: Enum + PartialEq + 'static> UiElement<BitFlipperParams> for Selector<T> {
+    fn prerender(
+        &mut self,
+        queue: &wgpu::Queue,
+        params: Arc<crate::BitFlipperParams>,
+        buffer: &[f32],
+    ) {
+        let index = (self.param)(&params).value().to_index();
+
+        if self.index != Some(index) {
+            self.label.change_text(&T::variants()[index].to_uppercase());
+            self.index = Some(index);
+        }
+
+        self.label.prerender(queue, params, buffer);
+    }
+
+    fn render(&self, render_pass: &mut wgpu::RenderPass) {
+        self.label.render(render_pass);
+    }
+}
+
+impl<T: Enum + PartialEq + 'static> UiBox for Selector<T> {
+    fn width(&self) -> u16 {
+        self.label.width()
+    }
+
+    fn height(&self) -> u16 {
+        self.label.height()
+    }
+
+    fn position(&self) -> (u16, u16) {
+        self.label.position()
+    }
+}
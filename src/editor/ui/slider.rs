@@ -1,10 +1,10 @@
-use super::texture::TextureAtlas;
+use super::{ParamGetter, texture::TextureAtlas};
 
 use crate::BitFlipperParams;
 use crate::editor::{VIEW_HEIGHT, VIEW_WIDTH, texture::UVSegment::*};
 
 use boxi::prelude::*;
-use nih_plug::params::Param;
+use nih_plug::prelude::*;
 use std::sync::Arc;
 use wgpu::util::DeviceExt;
 
@@ -13,7 +13,9 @@ use wgpu::util::DeviceExt;
 pub struct SliderUniforms {
     pub uv_region: [f32; 4],
     pub value: f32,
-    pub _padding: [f32; 3], // align to 16 bytes
+    /// The value after the host's modulation, shown as a ghost handle.
+    pub modulated: f32,
+    pub _padding: [f32; 2], // align to 16 bytes
 }
 
 pub struct SliderPipeline {
@@ -22,8 +24,47 @@ pub struct SliderPipeline {
     uniform_layout: wgpu::BindGroupLayout,
 }
 
+/// The parameter a [`Slider`] moves.
+#[derive(Debug, Clone, Copy)]
+pub enum SliderParam {
+    Float(ParamGetter<FloatParam>),
+    /// Snaps to whole steps, so a drag has to keep track of where it is in between.
+    Int(ParamGetter<IntParam>),
+}
+
+impl SliderParam {
+    pub fn normalized_value(self, params: &BitFlipperParams) -> f32 {
+        match self {
+            Self::Float(param) => param(params).unmodulated_normalized_value(),
+            Self::Int(param) => param(params).unmodulated_normalized_value(),
+        }
+    }
+
+    fn modulated_normalized_value(self, params: &BitFlipperParams) -> f32 {
+        match self {
+            Self::Float(param) => param(params).modulated_normalized_value(),
+            Self::Int(param) => param(params).modulated_normalized_value(),
+        }
+    }
+
+    /// Sets the parameter as a single gesture.
+    pub fn set_normalized(self, params: &BitFlipperParams, setter: &ParamSetter, value: f32) {
+        match self {
+            Self::Float(param) => set_normalized(setter, param(params), value),
+            Self::Int(param) => set_normalized(setter, param(params), value),
+        }
+    }
+}
+
+fn set_normalized<P: Param>(setter: &ParamSetter, param: &P, value: f32) {
+    setter.begin_set_parameter(param);
+    setter.set_parameter_normalized(param, value);
+    setter.end_set_parameter(param);
+}
+
 pub struct Slider {
     shared_pipeline: Arc<SliderPipeline>,
+    param: SliderParam,
     position: (u16, u16),
     position_buffer: wgpu::Buffer,
     uniform_buffer: wgpu::Buffer,
@@ -98,7 +139,31 @@ impl SliderPipeline {
 }
 
 impl Slider {
-    pub fn new(device: &wgpu::Device, position: (u16, u16), pipeline: Arc<SliderPipeline>) -> Self {
+    pub fn new(
+        device: &wgpu::Device,
+        position: (u16, u16),
+        pipeline: Arc<SliderPipeline>,
+        param: ParamGetter<FloatParam>,
+    ) -> Self {
+        Self::with_param(device, position, pipeline, SliderParam::Float(param))
+    }
+
+    /// A slider for a parameter that moves in whole steps.
+    pub fn int(
+        device: &wgpu::Device,
+        position: (u16, u16),
+        pipeline: Arc<SliderPipeline>,
+        param: ParamGetter<IntParam>,
+    ) -> Self {
+        Self::with_param(device, position, pipeline, SliderParam::Int(param))
+    }
+
+    fn with_param(
+        device: &wgpu::Device,
+        position: (u16, u16),
+        pipeline: Arc<SliderPipeline>,
+        param: SliderParam,
+    ) -> Self {
         let (x, y) = position;
         let pos_data = [x, y, x + 59, y + 8]
             .get_vertices::<{ VIEW_WIDTH as usize }, { VIEW_HEIGHT as usize }>();
@@ -113,7 +178,8 @@ impl Slider {
         let uniforms = SliderUniforms {
             uv_region,
             value: 0.0,
-            _padding: [0.0; 3],
+            modulated: 0.0,
+            _padding: [0.0; 2],
         };
 
         let uv_data = pipeline.tex_atlas.get_uvs(&UV_slider_handle).unwrap();
@@ -140,6 +206,7 @@ impl Slider {
 
         Self {
             position,
+            param,
 
             position_buffer,
             shared_pipeline: pipeline,
@@ -149,6 +216,10 @@ impl Slider {
             uv_buffer,
         }
     }
+
+    pub fn param(&self) -> SliderParam {
+        self.param
+    }
 }
 
 impl UiInteractive<BitFlipperParams> for Slider {}
@@ -159,8 +230,8 @@ impl UiElement<BitFlipperParams> for Slider {
         params: Arc<crate::BitFlipperParams>,
         _buffer: &[f32],
     ) {
-        let value = params.pre_gain.value();
-        let value = params.pre_gain.preview_normalized(value);
+        let value = self.param.normalized_value(&params);
+        let modulated = self.param.modulated_normalized_value(&params);
 
         queue.write_buffer(
             &self.uniform_buffer,
@@ -168,7 +239,8 @@ impl UiElement<BitFlipperParams> for Slider {
             bytemuck::bytes_of(&SliderUniforms {
                 uv_region: self.uv_region,
                 value,
-                _padding: [0.0; 3],
+                modulated,
+                _padding: [0.0; 2],
             }),
         );
     }
@@ -23,6 +23,7 @@ fn vs_main(
 struct SliderUniforms {
   uv_region: vec4<f32>,
   value: f32,
+  modulated: f32,
 };
 
 @group(1) @binding(0) var<uniform> uniforms: SliderUniforms;
@@ -31,8 +32,8 @@ const VIEW_W: f32 = 200.0;
 const THUMB_W: f32 = 19.0;
 const SLIDER_W: f32 = 59.0;
 
-@fragment
-fn fs_main(@location(0) uv_coords: vec2<f32>) -> @location(0) vec4<f32> {
+// Samples the handle as if it were at `value`
+fn handle(uv_coords: vec2<f32>, value: f32) -> vec4<f32> {
     let epsilon = 0.0001;
 
     let region_start = uniforms.uv_region.x;
@@ -40,7 +41,7 @@ fn fs_main(@location(0) uv_coords: vec2<f32>) -> @location(0) vec4<f32> {
 
     let normalized_uv = (uv_coords.x - region_start) / region_width;
     let scaled_uv = normalized_uv * (200.0 / 59.0) + (3800.0 / 3481.0);
-    let clamped = clamp(scaled_uv - uniforms.value * (259.0 / 59.0), 0.0, 1.0);
+    let clamped = clamp(scaled_uv - value * (259.0 / 59.0), 0.0, 1.0);
 
     let denorm_x = region_start + clamped * region_width;
     let final_x = clamp(denorm_x, region_start + epsilon, region_start + region_width - epsilon);
@@ -48,3 +49,12 @@ fn fs_main(@location(0) uv_coords: vec2<f32>) -> @location(0) vec4<f32> {
     let denormalized_uv = vec2(final_x, uv_coords.y);
     return textureSample(box_texture, box_sampler, denormalized_uv);
 }
+
+@fragment
+fn fs_main(@location(0) uv_coords: vec2<f32>) -> @location(0) vec4<f32> {
+    let color = handle(uv_coords, uniforms.value);
+    let ghost = handle(uv_coords, uniforms.modulated);
+
+    // The modulated handle shows through faintly wherever the real one isn't
+    return select(vec4(ghost.rgb, ghost.a * 0.4), color, color.a > 0.5);
+}
@@ -0,0 +1,107 @@
+use crate::BitFlipperParams;
+use crate::editor::texture::{TextureError, UVSegment};
+use crate::editor::ui::{Button, ColorBox, ColorBoxPipeline, SlotButton, StaticBoxPipeline};
+use crate::model::SNAPSHOT_SLOTS;
+
+use boxi::prelude::*;
+use std::sync::Arc;
+
+/// Dims slots that hold a snapshot less than empty ones.
+const STORED_DIM: [f32; 4] = [0.0, 0.0, 0.0, 0.4];
+const EMPTY_DIM: [f32; 4] = [0.0, 0.0, 0.0, 0.8];
+
+/// A button for each snapshot slot. Clicking one recalls it, shift-clicking stores the current
+/// mask and mode in it.
+pub struct SnapshotSlots {
+    pub buttons: Vec<SlotButton>,
+    backdrop: ColorBox,
+}
+
+impl SnapshotSlots {
+    pub fn new(
+        device: &wgpu::Device,
+        position: (u16, u16),
+        pipeline: Arc<StaticBoxPipeline>,
+        color_pipeline: Arc<ColorBoxPipeline>,
+    ) -> Result<Self, TextureError> {
+        let (x, y) = position;
+
+        let buttons = (0..SNAPSHOT_SLOTS)
+            .map(|i| {
+                let slot = i as i32 + 1;
+                let pos = (x + 2 + (i as u16 % 4) * 10, y + 2 + (i as u16 / 4) * 10);
+                let digit = char::from_digit(slot as u32, 10).unwrap();
+
+                let dim = ColorBox::new(device, pos, 6, 8, EMPTY_DIM, color_pipeline.clone());
+                let segment = UVSegment::from(digit);
+
+                Button::new(
+                    device,
+                    slot,
+                    |p| p.snapshot.slot.value(),
+                    &segment,
+                    pos,
+                    pipeline.clone(),
+                )
+                .map(|button| button.with_dim(dim))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            buttons,
+            backdrop: ColorBox::new(
+                device,
+                position,
+                42,
+                22,
+                [0.0, 0.0, 0.0, 0.8],
+                color_pipeline,
+            ),
+        })
+    }
+}
+
+impl UiInteractive<BitFlipperParams> for SnapshotSlots {}
+impl UiElement<BitFlipperParams> for SnapshotSlots {
+    fn prerender(
+        &mut self,
+        queue: &wgpu::Queue,
+        params: Arc<crate::BitFlipperParams>,
+        buffer: &[f32],
+    ) {
+        for button in self.buttons.iter_mut() {
+            button.prerender(queue, params.clone(), buffer);
+
+            let color = match params.snapshot.get(button.get_state()) {
+                Some(_) => STORED_DIM,
+                None => EMPTY_DIM,
+            };
+
+            if let Some(dim) = button.dim() {
+                dim.set_color(queue, color);
+            }
+        }
+    }
+
+    fn render(&self, render_pass: &mut wgpu::RenderPass) {
+        self.backdrop.render(render_pass);
+
+        for button in self.buttons.iter() {
+            button.render(render_pass);
+        }
+    }
+}
+
+impl UiBox for SnapshotSlots {
+    fn width(&self) -> u16 {
+        self.backdrop.width()
+    }
+
+    fn height(&self) -> u16 {
+        self.backdrop.height()
+    }
+
+    fn position(&self) -> (u16, u16) {
+        self.backdrop.position()
+    }
+}
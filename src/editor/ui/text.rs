@@ -1,10 +1,13 @@
 use crate::BitFlipperParams;
+use crate::bus::Bus;
 use crate::editor::texture::TextureError;
 use crate::editor::texture::UVSegment::{self, *};
+use crate::editor::ui::font::{GLYPH_HEIGHT, GLYPH_WIDTH, glyph};
 use crate::editor::ui::{StaticBox, StaticBoxPipeline};
 
 use boxi::prelude::*;
 use std::sync::Arc;
+use std::sync::atomic::Ordering;
 
 pub struct Text<const N: usize> {
     boxes: [StaticBox; N],
@@ -90,6 +93,87 @@ impl<const N: usize> UiElement<BitFlipperParams> for Text<N> {
     }
 }
 
+/// Like [`Text`], but for any text the built-in font can show. Needs a pipeline made with the
+/// font atlas.
+pub struct Label<const N: usize> {
+    boxes: [StaticBox; N],
+    content: [char; N],
+    position: (u16, u16),
+}
+
+impl<const N: usize> Label<N> {
+    pub fn new(
+        device: &wgpu::Device,
+        position: (u16, u16),
+        font_pipeline: Arc<StaticBoxPipeline>,
+    ) -> Result<Self, TextureError> {
+        let boxes: [StaticBox; N] = std::array::try_from_fn(|n| {
+            let pos = (position.0 + n as u16 * GLYPH_WIDTH, position.1);
+
+            StaticBox::new(device, &UV_glyph(' '), pos, font_pipeline.clone())
+        })?;
+
+        Ok(Self {
+            content: [' '; N],
+            boxes,
+            position,
+        })
+    }
+
+    /// For labels that always show the same text.
+    pub fn with_text(mut self, text: &str) -> Self {
+        self.change_text(text);
+        self
+    }
+
+    /// Text that doesn't fit is cut off.
+    pub fn change_text(&mut self, text: &str) {
+        for (ch, t_ch) in self
+            .content
+            .iter_mut()
+            .zip(text.chars().chain(std::iter::repeat(' ')))
+        {
+            *ch = glyph(t_ch);
+        }
+    }
+}
+
+impl<const N: usize> UiInteractive<BitFlipperParams> for Label<N> {}
+impl<const N: usize> UiElement<BitFlipperParams> for Label<N> {
+    fn prerender(
+        &mut self,
+        queue: &wgpu::Queue,
+        _params: Arc<crate::BitFlipperParams>,
+        _buffer: &[f32],
+    ) {
+        for (b, ch) in self.boxes.iter_mut().zip(self.content.iter()) {
+            b.swap_uv(queue, &UV_glyph(*ch)).ok();
+        }
+    }
+
+    fn render(&self, render_pass: &mut wgpu::RenderPass) {
+        for (b, ch) in self.boxes.iter().zip(self.content.iter()) {
+            if *ch != ' ' {
+                b.render(render_pass)
+            }
+        }
+    }
+}
+
+impl<const N: usize> UiBox for Label<N> {
+    fn width(&self) -> u16 {
+        N as u16 * GLYPH_WIDTH
+    }
+
+    fn height(&self) -> u16 {
+        GLYPH_HEIGHT
+    }
+
+    fn position(&self) -> (u16, u16) {
+        self.position
+    }
+}
+
 pub struct VolumeText {
     text: Text<8>,
 }
@@ -126,3 +210,46 @@ impl UiElement<BitFlipperParams> for VolumeText {
         self.text.render(render_pass);
     }
 }
+
+/// Shows the limiter's current gain reduction in decibels.
+pub struct ReductionText {
+    text: Text<8>,
+    bus: Arc<Bus>,
+}
+
+impl ReductionText {
+    pub fn new(
+        device: &wgpu::Device,
+        position: (u16, u16),
+        pipeline: Arc<StaticBoxPipeline>,
+        bus: Arc<Bus>,
+    ) -> Result<Self, TextureError> {
+        Ok(Self {
+            text: Text::new(device, position, pipeline)?,
+            bus,
+        })
+    }
+}
+
+impl UiElement<BitFlipperParams> for ReductionText {
+    fn prerender(
+        &mut self,
+        queue: &wgpu::Queue,
+        params: Arc<crate::BitFlipperParams>,
+        buffer: &[f32],
+    ) {
+        let reduction = self.bus.meters.limiter_reduction.load(Ordering::Relaxed);
+
+        let text = match params.limiter.enabled.value() {
+            true => format!("{:.1}dB", reduction.min(0.0)),
+            false => String::new(),
+        };
+
+        self.text.change_text(&text);
+        self.text.prerender(queue, params, buffer);
+    }
+
+    fn render(&self, render_pass: &mut wgpu::RenderPass) {
+        self.text.render(render_pass);
+    }
+}
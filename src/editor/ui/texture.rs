@@ -68,6 +68,8 @@ pub enum UVSegment {
     UV_num_minus,
     UV_num_d,
     UV_num_B,
+    /// A character of the built-in font, see [`super::font`].
+    UV_glyph(char),
 }
 
 impl UVSegment {
@@ -128,6 +130,16 @@ impl TextureAtlas {
     pub fn new(device: &wgpu::Device, texture: &[u8], queue: &wgpu::Queue) -> Self {
         let img = image::load_from_memory(texture).unwrap().to_rgba8();
 
+        Self::from_image(device, queue, &img, UV_MAP.iter().copied().collect())
+    }
+
+    /// Creates an atlas from an image built at runtime, with its own set of segments.
+    pub fn from_image(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        img: &image::RgbaImage,
+        bounds_map: HashMap<UVSegment, [u16; 4]>,
+    ) -> Self {
         let (width, height) = img.dimensions();
         let texture_size = wgpu::Extent3d {
             width,
@@ -153,7 +165,7 @@ impl TextureAtlas {
                 origin: wgpu::Origin3d::ZERO,
                 aspect: wgpu::TextureAspect::All,
             },
-            &img,
+            img,
             wgpu::TexelCopyBufferLayout {
                 offset: 0,
                 bytes_per_row: Some(4 * width),
@@ -202,16 +214,11 @@ impl TextureAtlas {
             ],
         });
 
-        let mut uv_map = HashMap::new();
-        UV_MAP.iter().for_each(|(k, v)| {
-            uv_map.insert(*k, *v);
-        });
-
         Self {
             bind_group,
             layout,
             tex_size: (width, height),
-            bounds_map: uv_map,
+            bounds_map,
         }
     }
 
@@ -0,0 +1,136 @@
+use super::{
+    ColorBox, ColorBoxPipeline, Label, ParamGetter, StaticBox, StaticBoxPipeline,
+    texture::TextureError,
+};
+
+use crate::BitFlipperParams;
+use crate::editor::texture::UVSegment::*;
+
+use boxi::prelude::*;
+use nih_plug::prelude::*;
+use std::sync::Arc;
+
+/// How a [`Toggle`] is drawn.
+enum Face {
+    /// A single lit or unlit digit.
+    Digit(StaticBox),
+    /// A short name that's dimmed while off.
+    Text(Label<3>, ColorBox),
+}
+
+/// An on/off switch for a [`BoolParam`].
+pub struct Toggle {
+    face: Face,
+    param: ParamGetter<BoolParam>,
+    is_on: bool,
+}
+
+impl Toggle {
+    pub fn new(
+        device: &wgpu::Device,
+        position: (u16, u16),
+        pipeline: Arc<StaticBoxPipeline>,
+        param: ParamGetter<BoolParam>,
+    ) -> Result<Self, TextureError> {
+        let static_box = StaticBox::new(device, &UV_digi_0_0, position, pipeline)?;
+
+        Ok(Self {
+            face: Face::Digit(static_box),
+            param,
+            is_on: false,
+        })
+    }
+
+    /// A toggle that shows `name` instead of a digit. Needs a pipeline made with the font atlas.
+    pub fn labeled(
+        device: &wgpu::Device,
+        position: (u16, u16),
+        name: &str,
+        font_pipeline: Arc<StaticBoxPipeline>,
+        color_pipeline: Arc<ColorBoxPipeline>,
+        param: ParamGetter<BoolParam>,
+    ) -> Result<Self, TextureError> {
+        let label = Label::new(device, position, font_pipeline)?.with_text(name);
+        let dim = ColorBox::new(
+            device,
+            position,
+            label.width(),
+            label.height(),
+            [0.0, 0.0, 0.0, 0.6],
+            color_pipeline,
+        );
+
+        Ok(Self {
+            face: Face::Text(label, dim),
+            param,
+            is_on: false,
+        })
+    }
+
+    pub fn param(&self) -> ParamGetter<BoolParam> {
+        self.param
+    }
+}
+
+impl UiInteractive<BitFlipperParams> for Toggle {}
+impl UiElement<BitFlipperParams> for Toggle {
+    fn prerender(
+        &mut self,
+        queue: &wgpu::Queue,
+        params: Arc<crate::BitFlipperParams>,
+        buffer: &[f32],
+    ) {
+        let val = (self.param)(&params).value();
+
+        match &mut self.face {
+            Face::Digit(static_box) => {
+                if self.is_on != val {
+                    let uv_id = if val { UV_digi_1_0 } else { UV_digi_0_0 };
+                    static_box.swap_uv(queue, &uv_id).ok();
+                }
+            }
+            Face::Text(label, _) => label.prerender(queue, params, buffer),
+        }
+
+        self.is_on = val;
+    }
+
+    fn render(&self, render_pass: &mut wgpu::RenderPass) {
+        match &self.face {
+            Face::Digit(static_box) => static_box.render(render_pass),
+            Face::Text(label, dim) => {
+                label.render(render_pass);
+
+                if !self.is_on {
+                    dim.render(render_pass);
+                }
+            }
+        }
+    }
+}
+
+impl UiBox for Toggle {
+    fn width(&self) -> u16 {
+        match &self.face {
+            Face::Digit(_) => 11,
+            Face::Text(label, _) => label.width(),
+        }
+    }
+
+    fn height(&self) -> u16 {
+        match &self.face {
+            Face::Digit(_) => 11,
+            Face::Text(label, _) => label.height(),
+        }
+    }
+
+    fn position(&self) -> (u16, u16) {
+        match &self.face {
+            Face::Digit(static_box) => {
+                let (x, y) = static_box.position();
+                (x - 1, y)
+            }
+            Face::Text(label, _) => label.position(),
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use crate::BitFlipperParams;
 use crate::editor::texture::{TextureError, UVSegment::*};
-use crate::editor::ui::{ColorBox, ColorBoxPipeline, StaticBox, StaticBoxPipeline};
+use crate::editor::ui::{ColorBox, ColorBoxPipeline, Label, StaticBox, StaticBoxPipeline};
 use crate::editor::{VIEW_HEIGHT, VIEW_WIDTH};
 
 use boxi::prelude::*;
@@ -10,6 +10,9 @@ pub struct Warning {
     position: (u16, u16),
     tex_box: StaticBox,
     color_box: ColorBox,
+    /// Toggles whether closing the warning hides it for good.
+    pub dont_show_again: Label<20>,
+    hide: bool,
 }
 
 impl Warning {
@@ -17,8 +20,11 @@ impl Warning {
         device: &wgpu::Device,
         position: (u16, u16),
         b_pipeline: Arc<StaticBoxPipeline>,
+        font_pipeline: Arc<StaticBoxPipeline>,
         c_pipeline: Arc<ColorBoxPipeline>,
     ) -> Result<Self, TextureError> {
+        let (x, y) = position;
+
         Ok(Self {
             position,
             tex_box: StaticBox::new(device, &UV_warning, position, b_pipeline)?,
@@ -30,15 +36,43 @@ impl Warning {
                 [0.0, 0.0, 0.0, 0.8],
                 c_pipeline,
             ),
+            // Centered below the warning
+            dont_show_again: Label::new(device, (x + 18, y + 92), font_pipeline)?,
+            hide: false,
         })
     }
+
+    pub fn toggle_hide(&mut self) {
+        self.hide = !self.hide;
+    }
+
+    /// Whether the warning should stay hidden once closed.
+    pub fn hide(&self) -> bool {
+        self.hide
+    }
 }
 
 impl UiInteractive<BitFlipperParams> for Warning {}
 impl UiElement<BitFlipperParams> for Warning {
+    fn prerender(
+        &mut self,
+        queue: &wgpu::Queue,
+        params: Arc<crate::BitFlipperParams>,
+        buffer: &[f32],
+    ) {
+        let text = match self.hide {
+            true => "[X] DON'T SHOW AGAIN",
+            false => "[ ] DON'T SHOW AGAIN",
+        };
+
+        self.dont_show_again.change_text(text);
+        self.dont_show_again.prerender(queue, params, buffer);
+    }
+
     fn render(&self, render_pass: &mut wgpu::RenderPass) {
         self.color_box.render(render_pass);
         self.tex_box.render(render_pass);
+        self.dont_show_again.render(render_pass);
     }
 }
 
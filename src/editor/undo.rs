@@ -0,0 +1,125 @@
+use crate::BitFlipperParams;
+use crate::state::sound_fields;
+
+use nih_plug::prelude::*;
+use std::collections::BTreeMap;
+
+/// Older entries are dropped once there are more than this.
+const MAX_ENTRIES: usize = 128;
+
+/// Normalized values closer than this count as unchanged.
+const EPSILON: f32 = 1e-6;
+
+struct Change {
+    param: ParamPtr,
+    before: f32,
+    after: f32,
+}
+
+/// The persisted fields from before and after a change to them, like storing a snapshot slot or
+/// capturing mask B.
+struct FieldChange {
+    before: BTreeMap<String, String>,
+    after: BTreeMap<String, String>,
+}
+
+struct Entry {
+    changes: Vec<Change>,
+    fields: Option<FieldChange>,
+}
+
+/// Changes made through the editor, both to the parameters and to the persisted fields that make
+/// up the sound. Everything that changes while the mouse is held down ends up in a single entry,
+/// so a whole slider drag is undone at once.
+#[derive(Default)]
+pub struct UndoStack {
+    undo: Vec<Entry>,
+    redo: Vec<Entry>,
+    /// The values and fields from before the interaction that is in progress.
+    pending: Option<(Vec<(ParamPtr, f32)>, BTreeMap<String, String>)>,
+}
+
+/// The unmodulated normalized value of every parameter.
+fn values(params: &BitFlipperParams) -> Vec<(ParamPtr, f32)> {
+    params
+        .param_map()
+        .into_iter()
+        // SAFETY: The pointers point into `params`, which outlives this function
+        .map(|(_, ptr, _)| (ptr, unsafe { ptr.unmodulated_normalized_value() }))
+        .collect()
+}
+
+impl UndoStack {
+    /// Remembers the current values. Does nothing if an interaction is already in progress.
+    pub fn begin(&mut self, params: &BitFlipperParams) {
+        self.pending
+            .get_or_insert_with(|| (values(params), sound_fields(params)));
+    }
+
+    /// Records whatever changed since [`Self::begin()`] as one entry.
+    pub fn end(&mut self, params: &BitFlipperParams) {
+        let Some((before, fields_before)) = self.pending.take() else {
+            return;
+        };
+
+        let changes: Vec<_> = before
+            .into_iter()
+            .zip(values(params))
+            .filter(|((_, before), (_, after))| (before - after).abs() > EPSILON)
+            .map(|((param, before), (_, after))| Change {
+                param,
+                before,
+                after,
+            })
+            .collect();
+
+        let fields_after = sound_fields(params);
+        let fields = (fields_after != fields_before).then_some(FieldChange {
+            before: fields_before,
+            after: fields_after,
+        });
+
+        if changes.is_empty() && fields.is_none() {
+            return;
+        }
+
+        if self.undo.len() == MAX_ENTRIES {
+            self.undo.remove(0);
+        }
+
+        self.undo.push(Entry { changes, fields });
+        self.redo.clear();
+    }
+
+    pub fn undo(&mut self, params: &BitFlipperParams, context: &dyn GuiContext) {
+        if let Some(entry) = self.undo.pop() {
+            apply(params, context, &entry, false);
+            self.redo.push(entry);
+        }
+    }
+
+    pub fn redo(&mut self, params: &BitFlipperParams, context: &dyn GuiContext) {
+        if let Some(entry) = self.redo.pop() {
+            apply(params, context, &entry, true);
+            self.undo.push(entry);
+        }
+    }
+}
+
+/// Puts back the state from before the entry, or from after it when `redo` is set.
+fn apply(params: &BitFlipperParams, context: &dyn GuiContext, entry: &Entry, redo: bool) {
+    for change in &entry.changes {
+        let value = if redo { change.after } else { change.before };
+
+        // SAFETY: The pointers come from the same parameters the context belongs to
+        unsafe {
+            context.raw_begin_set_parameter(change.param);
+            context.raw_set_parameter_normalized(change.param, value);
+            context.raw_end_set_parameter(change.param);
+        }
+    }
+
+    if let Some(fields) = &entry.fields {
+        params.deserialize_fields(if redo { &fields.after } else { &fields.before });
+    }
+}
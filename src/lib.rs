@@ -1,39 +1,134 @@
 #![feature(array_try_from_fn)]
 
-use editor::{CustomWgpuEditorState, VIEW_WIDTH, create_editor};
-use model::{BitParams, FlipModes};
+use crossbeam::atomic::AtomicCell;
+use dsp::{Processor, Settings};
+use editor::{CustomWgpuEditorState, create_editor};
+use model::{
+    BitParams, DecimatorParams, FlipModes, FreezeParams, GateParams, GeneratorParams,
+    HistoryParams, LimiterParams, MacroParams, Snapshot, SnapshotParams, TransitionParams,
+    TruncateParams,
+};
 use nih_plug::prelude::*;
+use state::STATE_VERSION;
+use std::ops::RangeInclusive;
 use std::sync::Arc;
+use std::sync::atomic::Ordering;
 
 use crate::bus::Bus;
 
 mod editor;
 
 pub(crate) mod bus;
-pub(crate) mod model;
+
+pub mod model;
+
+pub mod preset;
+
+pub(crate) mod settings;
+
+pub mod state;
+
+pub use bit_flipper_core as dsp;
 
 pub struct BitFlipper {
     params: Arc<BitFlipperParams>,
     bus: Arc<Bus>,
+
+    processor: Processor,
+    /// The latency last reported to the host, in samples.
+    latency: u32,
+    /// Whether the transport was playing during the last call to `process()`.
+    playing: bool,
+    /// Where the transport should be at the start of the next call if playback continues.
+    next_position: Option<i64>,
+    /// The mono input needs to be copied to the second output channel before processing.
+    upmix: bool,
+    /// Index of the LFE channel in surround layouts, which the editor's downmix and the spread
+    /// leave out.
+    lfe_channel: Option<usize>,
 }
 
 pub(crate) const UI_SCALE: usize = 3;
 
+const SIDECHAIN_PORT_NAMES: PortNames = PortNames {
+    aux_inputs: &["Sidechain"],
+    ..PortNames::const_default()
+};
+
+const SURROUND_5_1_PORT_NAMES: PortNames = PortNames {
+    layout: Some("5.1"),
+    ..SIDECHAIN_PORT_NAMES
+};
+
+const SURROUND_7_1_PORT_NAMES: PortNames = PortNames {
+    layout: Some("7.1"),
+    ..SIDECHAIN_PORT_NAMES
+};
+
+/// Where the LFE sits in the channel order of the 5.1 and 7.1 layouts.
+const LFE_CHANNEL: usize = 3;
+
 #[derive(Params)]
-struct BitFlipperParams {
+pub struct BitFlipperParams {
     /// The editor state, saved together with the parameter state so the custom scaling can be
     /// restored.
     #[persist = "editor-state"]
     editor_state: Arc<CustomWgpuEditorState>,
 
-    #[nested(group = "bits")]
+    /// The layout version of the saved state, see [`state`].
+    #[persist = "state-version"]
+    state_version: Arc<AtomicCell<u32>>,
+
+    #[id = "bypass"]
+    pub bypass: BoolParam,
+
+    #[nested(group = "Generator")]
+    pub generator: GeneratorParams,
+
+    #[nested(group = "Mask Bits")]
     pub bits: BitParams,
 
+    #[nested(group = "Macros")]
+    pub macros: MacroParams,
+
+    #[nested(group = "Snapshot")]
+    pub snapshot: SnapshotParams,
+
     #[id = "mode"]
     pub mode: EnumParam<FlipModes>,
 
+    /// Flips the bits of the sample converted to `f64`, using all 64 mask bits.
+    #[id = "double_precision"]
+    pub double_precision: BoolParam,
+
+    /// Rotates the mantissa mask differently for every channel to widen the result, instead of
+    /// linking all channels to the same mask. The LFE channel keeps the mask as is.
+    #[id = "channel_spread"]
+    pub spread: BoolParam,
+
     #[id = "pre_gain"]
     pub pre_gain: FloatParam,
+
+    #[nested(group = "Decimator")]
+    pub decimator: DecimatorParams,
+
+    #[nested(group = "Truncate")]
+    pub truncate: TruncateParams,
+
+    #[nested(group = "Freeze")]
+    pub freeze: FreezeParams,
+
+    #[nested(group = "Transition")]
+    pub transition: TransitionParams,
+
+    #[nested(group = "History")]
+    pub history: HistoryParams,
+
+    #[nested(group = "Gate")]
+    pub gate: GateParams,
+
+    #[nested(group = "Limiter")]
+    pub limiter: LimiterParams,
 }
 
 impl Default for BitFlipper {
@@ -41,24 +136,33 @@ impl Default for BitFlipper {
         Self {
             params: Arc::new(BitFlipperParams::default()),
             bus: Default::default(),
+
+            processor: Processor::default(),
+            latency: 0,
+            playing: false,
+            next_position: None,
+            upmix: false,
+            lfe_channel: None,
         }
     }
 }
 
 impl Default for BitFlipperParams {
     fn default() -> Self {
-        let size = (
-            VIEW_WIDTH as u32 * UI_SCALE as u32,
-            VIEW_WIDTH as u32 * UI_SCALE as u32,
-        );
-
         Self {
-            editor_state: CustomWgpuEditorState::from_size(size),
-            mode: EnumParam::new("mode", FlipModes::default()),
+            editor_state: CustomWgpuEditorState::new(),
+            state_version: Arc::new(AtomicCell::new(STATE_VERSION)),
+            mode: EnumParam::new("Mode", FlipModes::default()),
+            double_precision: BoolParam::new("Double Precision", false),
+            spread: BoolParam::new("Mantissa Spread", false),
+            bypass: BoolParam::new("Bypass", false).make_bypass(),
+            generator: GeneratorParams::default(),
             bits: BitParams::default(),
+            macros: MacroParams::default(),
+            snapshot: SnapshotParams::default(),
             pre_gain: FloatParam::new(
-                "pre_gain",
-                util::db_to_gain(0.0),
+                "Pre Gain",
+                dsp::defaults::PRE_GAIN,
                 FloatRange::Skewed {
                     min: util::db_to_gain(-30.0),
                     max: util::db_to_gain(30.0),
@@ -67,6 +171,71 @@ impl Default for BitFlipperParams {
             )
             .with_unit("dB")
             .with_value_to_string(formatters::v2s_f32_gain_to_db(2)),
+            decimator: DecimatorParams::default(),
+            truncate: TruncateParams::default(),
+            freeze: FreezeParams::default(),
+            transition: TransitionParams::default(),
+            history: HistoryParams::default(),
+            gate: GateParams::default(),
+            limiter: LimiterParams::default(),
+        }
+    }
+}
+
+impl BitFlipperParams {
+    /// The mask and mode the macros start from, taken from the recalled snapshot if there is one.
+    fn source(&self) -> Snapshot {
+        self.snapshot.recalled().unwrap_or_else(|| Snapshot {
+            mask: self.bits.to_u64(),
+            mode: self.mode.value(),
+        })
+    }
+
+    /// The mask the macros start from, taken from the recalled snapshot if there is one.
+    fn mask_bits(&self) -> u64 {
+        self.source().mask
+    }
+
+    /// The mode in use, taken from the recalled snapshot if there is one.
+    fn mode(&self) -> FlipModes {
+        self.source().mode
+    }
+
+    /// The mask bits as changed by the macros, which is what actually gets applied.
+    fn mask(&self) -> u64 {
+        self.with_macros(self.mask_bits())
+    }
+
+    fn with_macros(&self, mask_bits: u64) -> u64 {
+        match self.double_precision.value() {
+            true => self.macros.apply(mask_bits, 64),
+            false => self.macros.apply(mask_bits as u32 as u64, 32),
+        }
+    }
+
+    /// Reads the current parameter values into a snapshot for the processing chain.
+    fn settings(&self) -> Settings {
+        // Read once, so the mask and the mode come from the same snapshot
+        let source = self.source();
+
+        Settings {
+            generator: self.generator.settings(),
+            mask: self.with_macros(source.mask),
+            mode: source.mode.into(),
+            double_precision: self.double_precision.value(),
+            spread: self.spread.value(),
+            lfe_channel: None,
+            pre_gain: self.pre_gain.value(),
+            decimate: self.decimator.settings(),
+            truncate: self.truncate.settings(),
+            freeze: self.freeze.settings(),
+            tempo: None,
+            transition: self.transition.style.value().into(),
+            transition_ms: self.transition.time.value(),
+            history: self.history.settings(),
+            gate: self.gate.settings(),
+            limiter: self.limiter.settings(),
+            bypass: self.bypass.value(),
         }
     }
 }
@@ -83,11 +252,36 @@ impl Plugin for BitFlipper {
         AudioIOLayout {
             main_input_channels: NonZeroU32::new(2),
             main_output_channels: NonZeroU32::new(2),
+            aux_input_ports: &[new_nonzero_u32(2)],
+            names: SIDECHAIN_PORT_NAMES,
             ..AudioIOLayout::const_default()
         },
         AudioIOLayout {
             main_input_channels: NonZeroU32::new(1),
             main_output_channels: NonZeroU32::new(1),
+            aux_input_ports: &[new_nonzero_u32(1)],
+            names: SIDECHAIN_PORT_NAMES,
+            ..AudioIOLayout::const_default()
+        },
+        AudioIOLayout {
+            main_input_channels: NonZeroU32::new(1),
+            main_output_channels: NonZeroU32::new(2),
+            aux_input_ports: &[new_nonzero_u32(1)],
+            names: SIDECHAIN_PORT_NAMES,
+            ..AudioIOLayout::const_default()
+        },
+        AudioIOLayout {
+            main_input_channels: NonZeroU32::new(6),
+            main_output_channels: NonZeroU32::new(6),
+            aux_input_ports: &[new_nonzero_u32(6)],
+            names: SURROUND_5_1_PORT_NAMES,
+            ..AudioIOLayout::const_default()
+        },
+        AudioIOLayout {
+            main_input_channels: NonZeroU32::new(8),
+            main_output_channels: NonZeroU32::new(8),
+            aux_input_ports: &[new_nonzero_u32(8)],
+            names: SURROUND_7_1_PORT_NAMES,
             ..AudioIOLayout::const_default()
         },
     ];
@@ -101,15 +295,41 @@ impl Plugin for BitFlipper {
         self.params.clone()
     }
 
+    fn filter_state(state: &mut PluginState) {
+        state::migrate(state);
+    }
+
     fn initialize(
         &mut self,
-        _audio_io_layout: &AudioIOLayout,
-        _buffer_config: &BufferConfig,
-        _context: &mut impl InitContext<Self>,
+        audio_io_layout: &AudioIOLayout,
+        buffer_config: &BufferConfig,
+        context: &mut impl InitContext<Self>,
     ) -> bool {
+        let inputs = audio_io_layout
+            .main_input_channels
+            .map_or(0, NonZeroU32::get);
+        let outputs = audio_io_layout
+            .main_output_channels
+            .map_or(0, NonZeroU32::get);
+
+        self.upmix = inputs == 1 && outputs == 2;
+        self.lfe_channel = (outputs >= 6).then_some(LFE_CHANNEL);
+
+        self.processor.set_sample_rate(buffer_config.sample_rate);
+        self.processor.reset();
+
+        self.latency = self.processor.latency(&self.params.settings()) as u32;
+        context.set_latency_samples(self.latency);
+
         true
     }
 
+    fn reset(&mut self) {
+        self.processor.reset();
+        self.playing = false;
+        self.next_position = None;
+    }
+
     fn editor(&mut self, _async_executor: AsyncExecutor<Self>) -> Option<Box<dyn Editor>> {
         create_editor(&self.params, &self.bus)
     }
@@ -117,22 +337,57 @@ impl Plugin for BitFlipper {
     fn process(
         &mut self,
         buffer: &mut Buffer,
-        _aux: &mut AuxiliaryBuffers,
-        _context: &mut impl ProcessContext<Self>,
+        aux: &mut AuxiliaryBuffers,
+        context: &mut impl ProcessContext<Self>,
     ) -> ProcessStatus {
-        for channel_samples in buffer.iter_samples() {
-            let mask = self.params.bits.to_u32();
-            let mode = self.params.mode.value();
-            let gain = self.params.pre_gain.smoothed.next();
-
-            for sample in channel_samples {
-                *sample *= gain;
-                *sample = mode.transform(*sample, mask);
-            }
+        // Sample accurate automation already splits the buffer wherever a parameter changes or gets
+        // modulated, so the parameters only need to be read once per call. The values include the
+        // host's modulation.
+        let mut settings = self.params.settings();
+
+        let transport = context.transport();
+        settings.tempo = transport.tempo.map(|tempo| tempo as f32);
+        settings.lfe_channel = self.lfe_channel;
+
+        // Starting playback or jumping around the timeline restarts the stateful stages, so the
+        // same position always renders the same way
+        let position = transport.pos_samples();
+        let started = transport.playing && !self.playing;
+        let jumped = transport.playing && position != self.next_position;
+        if started || jumped {
+            self.processor.restart(position.unwrap_or_default());
+        }
+
+        self.playing = transport.playing;
+        self.next_position = position.map(|position| position + buffer.samples() as i64);
+
+        let latency = self.processor.latency(&settings) as u32;
+        if latency != self.latency {
+            self.latency = latency;
+            context.set_latency_samples(latency);
+        }
+
+        if self.upmix
+            && let [mono, right] = buffer.as_slice()
+        {
+            right.copy_from_slice(mono);
         }
 
+        let sidechain = aux.inputs.first().map(|input| input.as_slice_immutable());
+        self.processor
+            .process(buffer.as_slice(), sidechain, &settings);
+
+        let meters = &self.bus.meters;
+        meters
+            .gate_closed
+            .store(self.processor.gate_closed(&settings), Ordering::Relaxed);
+        meters.limiter_reduction.store(
+            util::gain_to_db(self.processor.limiter_gain()),
+            Ordering::Relaxed,
+        );
+
         if self.params.editor_state.is_open() {
-            self.bus.send_buffer_summing(buffer);
+            self.bus.send_buffer_summing(buffer, self.lfe_channel);
         }
 
         ProcessStatus::Normal
@@ -148,8 +403,99 @@ impl ClapPlugin for BitFlipper {
         ClapFeature::AudioEffect,
         ClapFeature::Stereo,
         ClapFeature::Mono,
+        ClapFeature::Surround,
         ClapFeature::Utility,
     ];
+
+    fn remote_controls(&self, context: &mut impl RemoteControlsContext) {
+        let params = &self.params;
+
+        context.add_section("Bit Flipper", |section| {
+            section.add_page("Mode/Gain", |page| {
+                page.add_param(&params.mode);
+                page.add_param(&params.pre_gain);
+                page.add_param(&params.double_precision);
+                page.add_param(&params.spread);
+                page.add_param(&params.macros.amount);
+                page.add_param(&params.macros.morph);
+                page.add_param(&params.snapshot.slot);
+                page.add_param(&params.bypass);
+            });
+
+            // Eight bits to a page, longer pages would get split up by the wrapper with less
+            // telling names
+            for (name, bits) in [
+                ("Sign/Exp 8-2", 25..=32),
+                ("Exp 1/Man 23-17", 17..=24),
+                ("Man 16-9", 9..=16),
+                ("Man 8-1", 1..=8),
+            ] {
+                section.add_page(name, |page| add_bits(page, &params.bits, bits.clone()));
+            }
+        });
+
+        context.add_section("Double Precision", |section| {
+            for (name, bits) in [
+                ("Sign/Exp 11-5", 57..=64),
+                ("Exp 4-1/Man 52-49", 49..=56),
+                ("Man 48-41", 41..=48),
+                ("Man 40-33", 33..=40),
+            ] {
+                section.add_page(name, |page| add_bits(page, &params.bits, bits.clone()));
+            }
+        });
+
+        context.add_section("Effects", |section| {
+            section.add_page("Decimator/Truncate", |page| {
+                page.add_param(&params.decimator.enabled);
+                page.add_param(&params.decimator.rate);
+                page.add_param(&params.decimator.filter);
+                page.add_spacer();
+                page.add_param(&params.truncate.enabled);
+                page.add_param(&params.truncate.depth);
+            });
+            section.add_page("Gate", |page| {
+                page.add_param(&params.gate.enabled);
+                page.add_param(&params.gate.threshold);
+                page.add_param(&params.gate.hysteresis);
+                page.add_param(&params.gate.attack);
+                page.add_param(&params.gate.release);
+                page.add_param(&params.gate.action);
+                page.add_param(&params.gate.sidechain);
+            });
+            section.add_page("Limiter", |page| {
+                page.add_param(&params.limiter.enabled);
+                page.add_param(&params.limiter.ceiling);
+                page.add_param(&params.limiter.release);
+            });
+            section.add_page("Freeze/History", |page| {
+                page.add_param(&params.freeze.enabled);
+                page.add_param(&params.freeze.time);
+                page.add_param(&params.freeze.sync);
+                page.add_param(&params.freeze.division);
+                page.add_param(&params.history.enabled);
+                page.add_param(&params.history.mode);
+                page.add_param(&params.history.delay);
+                page.add_param(&params.history.reset);
+            });
+            section.add_page("Generator", |page| {
+                page.add_param(&params.generator.enabled);
+                page.add_param(&params.generator.waveform);
+                page.add_param(&params.generator.frequency);
+                page.add_param(&params.generator.level);
+                page.add_param(&params.generator.replace);
+            });
+        });
+    }
+}
+
+/// Adds the mask bits in `range` to a remote controls page, most significant first.
+fn add_bits(page: &mut impl RemoteControlsPage, bits: &BitParams, range: RangeInclusive<u8>) {
+    for bit in range.rev() {
+        if let Some(param) = bits.get_bit_param(bit) {
+            page.add_param(param);
+        }
+    }
 }
 
 impl Vst3Plugin for BitFlipper {
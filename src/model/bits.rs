@@ -69,11 +69,91 @@ pub struct BitParams {
     pub mask_bit_31: BoolParam,
     #[id = "mask_bit_32"]
     pub mask_bit_32: BoolParam,
+    #[id = "mask_bit_33"]
+    pub mask_bit_33: BoolParam,
+    #[id = "mask_bit_34"]
+    pub mask_bit_34: BoolParam,
+    #[id = "mask_bit_35"]
+    pub mask_bit_35: BoolParam,
+    #[id = "mask_bit_36"]
+    pub mask_bit_36: BoolParam,
+    #[id = "mask_bit_37"]
+    pub mask_bit_37: BoolParam,
+    #[id = "mask_bit_38"]
+    pub mask_bit_38: BoolParam,
+    #[id = "mask_bit_39"]
+    pub mask_bit_39: BoolParam,
+
+    #[id = "mask_bit_40"]
+    pub mask_bit_40: BoolParam,
+    #[id = "mask_bit_41"]
+    pub mask_bit_41: BoolParam,
+    #[id = "mask_bit_42"]
+    pub mask_bit_42: BoolParam,
+    #[id = "mask_bit_43"]
+    pub mask_bit_43: BoolParam,
+    #[id = "mask_bit_44"]
+    pub mask_bit_44: BoolParam,
+    #[id = "mask_bit_45"]
+    pub mask_bit_45: BoolParam,
+    #[id = "mask_bit_46"]
+    pub mask_bit_46: BoolParam,
+    #[id = "mask_bit_47"]
+    pub mask_bit_47: BoolParam,
+    #[id = "mask_bit_48"]
+    pub mask_bit_48: BoolParam,
+    #[id = "mask_bit_49"]
+    pub mask_bit_49: BoolParam,
+
+    #[id = "mask_bit_50"]
+    pub mask_bit_50: BoolParam,
+    #[id = "mask_bit_51"]
+    pub mask_bit_51: BoolParam,
+    #[id = "mask_bit_52"]
+    pub mask_bit_52: BoolParam,
+    #[id = "mask_bit_53"]
+    pub mask_bit_53: BoolParam,
+    #[id = "mask_bit_54"]
+    pub mask_bit_54: BoolParam,
+    #[id = "mask_bit_55"]
+    pub mask_bit_55: BoolParam,
+    #[id = "mask_bit_56"]
+    pub mask_bit_56: BoolParam,
+    #[id = "mask_bit_57"]
+    pub mask_bit_57: BoolParam,
+    #[id = "mask_bit_58"]
+    pub mask_bit_58: BoolParam,
+    #[id = "mask_bit_59"]
+    pub mask_bit_59: BoolParam,
+
+    #[id = "mask_bit_60"]
+    pub mask_bit_60: BoolParam,
+    #[id = "mask_bit_61"]
+    pub mask_bit_61: BoolParam,
+    #[id = "mask_bit_62"]
+    pub mask_bit_62: BoolParam,
+    #[id = "mask_bit_63"]
+    pub mask_bit_63: BoolParam,
+    #[id = "mask_bit_64"]
+    pub mask_bit_64: BoolParam,
+}
+
+/// The name hosts show for a mask bit, counting from 1. The first 32 bits are named after the part
+/// of a single precision float they flip, the others after the part of a double they flip.
+pub fn bit_name(bit: u8) -> String {
+    match bit {
+        1..=23 => format!("Man {bit}"),
+        24..=31 => format!("Exp {}", bit - 23),
+        32 => String::from("Sign"),
+        33..=52 => format!("Dbl Man {bit}"),
+        53..=63 => format!("Dbl Exp {}", bit - 52),
+        _ => String::from("Dbl Sign"),
+    }
 }
 
 macro_rules! add_bit {
     ($result:ident, $self:ident, $field:ident, $bit:expr) => {
-        $result |= ($self.$field.value() as u32) << $bit;
+        $result |= ($self.$field.value() as u64) << $bit;
     };
 }
 
@@ -112,12 +192,71 @@ impl BitParams {
             30 => Some(&self.mask_bit_30),
             31 => Some(&self.mask_bit_31),
             32 => Some(&self.mask_bit_32),
+            33 => Some(&self.mask_bit_33),
+            34 => Some(&self.mask_bit_34),
+            35 => Some(&self.mask_bit_35),
+            36 => Some(&self.mask_bit_36),
+            37 => Some(&self.mask_bit_37),
+            38 => Some(&self.mask_bit_38),
+            39 => Some(&self.mask_bit_39),
+            40 => Some(&self.mask_bit_40),
+            41 => Some(&self.mask_bit_41),
+            42 => Some(&self.mask_bit_42),
+            43 => Some(&self.mask_bit_43),
+            44 => Some(&self.mask_bit_44),
+            45 => Some(&self.mask_bit_45),
+            46 => Some(&self.mask_bit_46),
+            47 => Some(&self.mask_bit_47),
+            48 => Some(&self.mask_bit_48),
+            49 => Some(&self.mask_bit_49),
+            50 => Some(&self.mask_bit_50),
+            51 => Some(&self.mask_bit_51),
+            52 => Some(&self.mask_bit_52),
+            53 => Some(&self.mask_bit_53),
+            54 => Some(&self.mask_bit_54),
+            55 => Some(&self.mask_bit_55),
+            56 => Some(&self.mask_bit_56),
+            57 => Some(&self.mask_bit_57),
+            58 => Some(&self.mask_bit_58),
+            59 => Some(&self.mask_bit_59),
+            60 => Some(&self.mask_bit_60),
+            61 => Some(&self.mask_bit_61),
+            62 => Some(&self.mask_bit_62),
+            63 => Some(&self.mask_bit_63),
+            64 => Some(&self.mask_bit_64),
             _ => None,
         }
     }
 
+    /// Changes the bits that differ from `mask`, the same way clicking their digits would.
+    pub fn set_mask(&self, setter: &ParamSetter, mask: u64) {
+        let changed = self.to_u64() ^ mask;
+
+        for id in (1..=64u8).filter(|id| (changed >> (id - 1)) & 1 == 1) {
+            if let Some(param) = self.get_bit_param(id) {
+                setter.begin_set_parameter(param);
+                setter.set_parameter(param, (mask >> (id - 1)) & 1 == 1);
+                setter.end_set_parameter(param);
+            }
+        }
+    }
+
+    /// The bits the host's modulation currently flips, as a mask.
+    pub fn modulated(&self) -> u64 {
+        (1..=64u8)
+            .filter_map(|id| self.get_bit_param(id).map(|param| (id, param)))
+            .filter(|(_, param)| param.value() != param.unmodulated_plain_value())
+            .fold(0, |mask, (id, _)| mask | 1 << (id - 1))
+    }
+
+    /// The mask for single precision processing, made of the first 32 bits.
     pub fn to_u32(&self) -> u32 {
-        let mut result = 0u32;
+        self.to_u64() as u32
+    }
+
+    /// The mask for double precision processing, made of all 64 bits.
+    pub fn to_u64(&self) -> u64 {
+        let mut result = 0u64;
 
         add_bit!(result, self, mask_bit_1, 0);
         add_bit!(result, self, mask_bit_2, 1);
@@ -151,6 +290,38 @@ impl BitParams {
         add_bit!(result, self, mask_bit_30, 29);
         add_bit!(result, self, mask_bit_31, 30);
         add_bit!(result, self, mask_bit_32, 31);
+        add_bit!(result, self, mask_bit_33, 32);
+        add_bit!(result, self, mask_bit_34, 33);
+        add_bit!(result, self, mask_bit_35, 34);
+        add_bit!(result, self, mask_bit_36, 35);
+        add_bit!(result, self, mask_bit_37, 36);
+        add_bit!(result, self, mask_bit_38, 37);
+        add_bit!(result, self, mask_bit_39, 38);
+        add_bit!(result, self, mask_bit_40, 39);
+        add_bit!(result, self, mask_bit_41, 40);
+        add_bit!(result, self, mask_bit_42, 41);
+        add_bit!(result, self, mask_bit_43, 42);
+        add_bit!(result, self, mask_bit_44, 43);
+        add_bit!(result, self, mask_bit_45, 44);
+        add_bit!(result, self, mask_bit_46, 45);
+        add_bit!(result, self, mask_bit_47, 46);
+        add_bit!(result, self, mask_bit_48, 47);
+        add_bit!(result, self, mask_bit_49, 48);
+        add_bit!(result, self, mask_bit_50, 49);
+        add_bit!(result, self, mask_bit_51, 50);
+        add_bit!(result, self, mask_bit_52, 51);
+        add_bit!(result, self, mask_bit_53, 52);
+        add_bit!(result, self, mask_bit_54, 53);
+        add_bit!(result, self, mask_bit_55, 54);
+        add_bit!(result, self, mask_bit_56, 55);
+        add_bit!(result, self, mask_bit_57, 56);
+        add_bit!(result, self, mask_bit_58, 57);
+        add_bit!(result, self, mask_bit_59, 58);
+        add_bit!(result, self, mask_bit_60, 59);
+        add_bit!(result, self, mask_bit_61, 60);
+        add_bit!(result, self, mask_bit_62, 61);
+        add_bit!(result, self, mask_bit_63, 62);
+        add_bit!(result, self, mask_bit_64, 63);
 
         result
     }
@@ -159,41 +330,76 @@ impl BitParams {
 impl Default for BitParams {
     fn default() -> Self {
         Self {
-            mask_bit_1: BoolParam::new("mask_bit_1", false),
-            mask_bit_2: BoolParam::new("mask_bit_2", false),
-            mask_bit_3: BoolParam::new("mask_bit_3", false),
-            mask_bit_4: BoolParam::new("mask_bit_4", false),
-            mask_bit_5: BoolParam::new("mask_bit_5", false),
-            mask_bit_6: BoolParam::new("mask_bit_6", false),
-            mask_bit_7: BoolParam::new("mask_bit_7", false),
-            mask_bit_8: BoolParam::new("mask_bit_8", false),
-            mask_bit_9: BoolParam::new("mask_bit_9", false),
-
-            mask_bit_10: BoolParam::new("mask_bit_10", false),
-            mask_bit_11: BoolParam::new("mask_bit_11", false),
-            mask_bit_12: BoolParam::new("mask_bit_12", false),
-            mask_bit_13: BoolParam::new("mask_bit_13", false),
-            mask_bit_14: BoolParam::new("mask_bit_14", false),
-            mask_bit_15: BoolParam::new("mask_bit_15", false),
-            mask_bit_16: BoolParam::new("mask_bit_16", false),
-            mask_bit_17: BoolParam::new("mask_bit_17", false),
-            mask_bit_18: BoolParam::new("mask_bit_18", false),
-            mask_bit_19: BoolParam::new("mask_bit_19", false),
-
-            mask_bit_20: BoolParam::new("mask_bit_20", false),
-            mask_bit_21: BoolParam::new("mask_bit_21", false),
-            mask_bit_22: BoolParam::new("mask_bit_22", false),
-            mask_bit_23: BoolParam::new("mask_bit_23", false),
-            mask_bit_24: BoolParam::new("mask_bit_24", false),
-            mask_bit_25: BoolParam::new("mask_bit_25", false),
-            mask_bit_26: BoolParam::new("mask_bit_26", false),
-            mask_bit_27: BoolParam::new("mask_bit_27", false),
-            mask_bit_28: BoolParam::new("mask_bit_28", false),
-            mask_bit_29: BoolParam::new("mask_bit_29", false),
-
-            mask_bit_30: BoolParam::new("mask_bit_30", false),
-            mask_bit_31: BoolParam::new("mask_bit_31", false),
-            mask_bit_32: BoolParam::new("mask_bit_32", false),
+            mask_bit_1: BoolParam::new(bit_name(1), false),
+            mask_bit_2: BoolParam::new(bit_name(2), false),
+            mask_bit_3: BoolParam::new(bit_name(3), false),
+            mask_bit_4: BoolParam::new(bit_name(4), false),
+            mask_bit_5: BoolParam::new(bit_name(5), false),
+            mask_bit_6: BoolParam::new(bit_name(6), false),
+            mask_bit_7: BoolParam::new(bit_name(7), false),
+            mask_bit_8: BoolParam::new(bit_name(8), false),
+            mask_bit_9: BoolParam::new(bit_name(9), false),
+
+            mask_bit_10: BoolParam::new(bit_name(10), false),
+            mask_bit_11: BoolParam::new(bit_name(11), false),
+            mask_bit_12: BoolParam::new(bit_name(12), false),
+            mask_bit_13: BoolParam::new(bit_name(13), false),
+            mask_bit_14: BoolParam::new(bit_name(14), false),
+            mask_bit_15: BoolParam::new(bit_name(15), false),
+            mask_bit_16: BoolParam::new(bit_name(16), false),
+            mask_bit_17: BoolParam::new(bit_name(17), false),
+            mask_bit_18: BoolParam::new(bit_name(18), false),
+            mask_bit_19: BoolParam::new(bit_name(19), false),
+
+            mask_bit_20: BoolParam::new(bit_name(20), false),
+            mask_bit_21: BoolParam::new(bit_name(21), false),
+            mask_bit_22: BoolParam::new(bit_name(22), false),
+            mask_bit_23: BoolParam::new(bit_name(23), false),
+            mask_bit_24: BoolParam::new(bit_name(24), false),
+            mask_bit_25: BoolParam::new(bit_name(25), false),
+            mask_bit_26: BoolParam::new(bit_name(26), false),
+            mask_bit_27: BoolParam::new(bit_name(27), false),
+            mask_bit_28: BoolParam::new(bit_name(28), false),
+            mask_bit_29: BoolParam::new(bit_name(29), false),
+
+            mask_bit_30: BoolParam::new(bit_name(30), false),
+            mask_bit_31: BoolParam::new(bit_name(31), false),
+            mask_bit_32: BoolParam::new(bit_name(32), false),
+            mask_bit_33: BoolParam::new(bit_name(33), false),
+            mask_bit_34: BoolParam::new(bit_name(34), false),
+            mask_bit_35: BoolParam::new(bit_name(35), false),
+            mask_bit_36: BoolParam::new(bit_name(36), false),
+            mask_bit_37: BoolParam::new(bit_name(37), false),
+            mask_bit_38: BoolParam::new(bit_name(38), false),
+            mask_bit_39: BoolParam::new(bit_name(39), false),
+
+            mask_bit_40: BoolParam::new(bit_name(40), false),
+            mask_bit_41: BoolParam::new(bit_name(41), false),
+            mask_bit_42: BoolParam::new(bit_name(42), false),
+            mask_bit_43: BoolParam::new(bit_name(43), false),
+            mask_bit_44: BoolParam::new(bit_name(44), false),
+            mask_bit_45: BoolParam::new(bit_name(45), false),
+            mask_bit_46: BoolParam::new(bit_name(46), false),
+            mask_bit_47: BoolParam::new(bit_name(47), false),
+            mask_bit_48: BoolParam::new(bit_name(48), false),
+            mask_bit_49: BoolParam::new(bit_name(49), false),
+
+            mask_bit_50: BoolParam::new(bit_name(50), false),
+            mask_bit_51: BoolParam::new(bit_name(51), false),
+            mask_bit_52: BoolParam::new(bit_name(52), false),
+            mask_bit_53: BoolParam::new(bit_name(53), false),
+            mask_bit_54: BoolParam::new(bit_name(54), false),
+            mask_bit_55: BoolParam::new(bit_name(55), false),
+            mask_bit_56: BoolParam::new(bit_name(56), false),
+            mask_bit_57: BoolParam::new(bit_name(57), false),
+            mask_bit_58: BoolParam::new(bit_name(58), false),
+            mask_bit_59: BoolParam::new(bit_name(59), false),
+
+            mask_bit_60: BoolParam::new(bit_name(60), false),
+            mask_bit_61: BoolParam::new(bit_name(61), false),
+            mask_bit_62: BoolParam::new(bit_name(62), false),
+            mask_bit_63: BoolParam::new(bit_name(63), false),
+            mask_bit_64: BoolParam::new(bit_name(64), false),
         }
     }
 }
@@ -0,0 +1,46 @@
+use crate::dsp::{DecimateSettings, defaults};
+
+use nih_plug::prelude::*;
+
+#[derive(Params, Debug)]
+pub struct DecimatorParams {
+    #[id = "decimate_on"]
+    pub enabled: BoolParam,
+
+    /// How many input samples each held sample spans. Fractional values are allowed.
+    #[id = "decimate_rate"]
+    pub rate: FloatParam,
+
+    /// Low-pass the signal before sampling it to tame aliasing.
+    #[id = "decimate_filter"]
+    pub filter: BoolParam,
+}
+
+impl Default for DecimatorParams {
+    fn default() -> Self {
+        Self {
+            enabled: BoolParam::new("Decimator", false),
+            rate: FloatParam::new(
+                "Decimator Rate",
+                defaults::DECIMATE_RATE,
+                FloatRange::Skewed {
+                    min: 1.0,
+                    max: 64.0,
+                    factor: FloatRange::skew_factor(-1.5),
+                },
+            )
+            .with_unit("x")
+            .with_value_to_string(formatters::v2s_f32_rounded(2)),
+            filter: BoolParam::new("Decimator Filter", defaults::DECIMATE_FILTER),
+        }
+    }
+}
+
+impl DecimatorParams {
+    pub fn settings(&self) -> Option<DecimateSettings> {
+        self.enabled.value().then(|| DecimateSettings {
+            rate: self.rate.value(),
+            filter: self.filter.value(),
+        })
+    }
+}
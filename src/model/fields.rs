@@ -0,0 +1,174 @@
+use std::ops::RangeInclusive;
+
+/// The parts of an IEEE 754 float, as laid out over the mask bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FloatField {
+    Sign,
+    Exponent,
+    Mantissa,
+}
+
+impl FloatField {
+    pub const ALL: [Self; 3] = [Self::Sign, Self::Exponent, Self::Mantissa];
+
+    /// The mask bits the field covers, counting from 1.
+    pub fn bits(self, double_precision: bool) -> RangeInclusive<u8> {
+        match (self, double_precision) {
+            (Self::Sign, false) => 32..=32,
+            (Self::Exponent, false) => 24..=31,
+            (Self::Mantissa, false) => 1..=23,
+            (Self::Sign, true) => 64..=64,
+            (Self::Exponent, true) => 53..=63,
+            (Self::Mantissa, true) => 1..=52,
+        }
+    }
+
+    pub fn len(self, double_precision: bool) -> u8 {
+        let bits = self.bits(double_precision);
+
+        bits.end() - bits.start() + 1
+    }
+
+    /// The field's bits within the 64-bit mask.
+    pub fn mask(self, double_precision: bool) -> u64 {
+        let bits = self.bits(double_precision);
+
+        (u64::MAX >> (64 - self.len(double_precision))) << (bits.start() - 1)
+    }
+
+    /// The part of `mask` that falls in the field, shifted down to start at bit 0.
+    pub fn extract(self, mask: u64, double_precision: bool) -> u64 {
+        (mask & self.mask(double_precision)) >> (self.bits(double_precision).start() - 1)
+    }
+}
+
+/// Edits a whole field of the mask at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldOp {
+    Clear(FloatField),
+    Invert(FloatField),
+    /// Sets the field's `n` most significant bits and clears the rest of it.
+    SetTop(FloatField, u8),
+}
+
+impl FieldOp {
+    pub fn apply(self, mask: u64, double_precision: bool) -> u64 {
+        match self {
+            Self::Clear(field) => mask & !field.mask(double_precision),
+            Self::Invert(field) => mask ^ field.mask(double_precision),
+            Self::SetTop(field, n) => {
+                let field_mask = field.mask(double_precision);
+                let n = n.min(field.len(double_precision));
+                let top = match n {
+                    0 => 0,
+                    n => (u64::MAX << (64 - n)) >> (64 - field.bits(double_precision).end()),
+                };
+
+                (mask & !field_mask) | top
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fields_line_up_with_the_float_layout() {
+        assert_eq!(FloatField::Sign.mask(false), 0x8000_0000);
+        assert_eq!(FloatField::Exponent.mask(false), 0x7f80_0000);
+        assert_eq!(FloatField::Mantissa.mask(false), 0x007f_ffff);
+
+        assert_eq!(FloatField::Sign.mask(true), 1 << 63);
+        assert_eq!(FloatField::Exponent.mask(true), 0x7ff0_0000_0000_0000);
+        assert_eq!(FloatField::Mantissa.mask(true), (1 << 52) - 1);
+    }
+
+    #[test]
+    fn fields_cover_every_bit_once() {
+        for (double_precision, all) in [(false, u32::MAX as u64), (true, u64::MAX)] {
+            let masks = FloatField::ALL.map(|field| field.mask(double_precision));
+
+            assert_eq!(masks.iter().fold(0, |acc, mask| acc | mask), all);
+            assert_eq!(
+                masks.iter().map(|mask| mask.count_ones()).sum::<u32>(),
+                all.count_ones()
+            );
+        }
+    }
+
+    #[test]
+    fn extract_shifts_the_field_down() {
+        let one = 1.0f32.to_bits() as u64;
+        assert_eq!(FloatField::Exponent.extract(one, false), 127);
+        assert_eq!(FloatField::Mantissa.extract(one, false), 0);
+
+        let minus_one = (-1.0f64).to_bits();
+        assert_eq!(FloatField::Sign.extract(minus_one, true), 1);
+        assert_eq!(FloatField::Exponent.extract(minus_one, true), 1023);
+
+        for double_precision in [false, true] {
+            for field in FloatField::ALL {
+                let len = field.len(double_precision);
+
+                assert_eq!(
+                    field.extract(u64::MAX, double_precision),
+                    u64::MAX >> (64 - len)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn clear_and_invert_only_touch_the_field() {
+        let mask = 0x5555_5555_5555_5555;
+
+        for double_precision in [false, true] {
+            for field in FloatField::ALL {
+                let field_mask = field.mask(double_precision);
+
+                let cleared = FieldOp::Clear(field).apply(mask, double_precision);
+                assert_eq!(cleared, mask & !field_mask);
+
+                let inverted = FieldOp::Invert(field).apply(mask, double_precision);
+                assert_eq!(inverted & field_mask, !mask & field_mask);
+                assert_eq!(inverted & !field_mask, mask & !field_mask);
+            }
+        }
+    }
+
+    #[test]
+    fn set_top_sets_the_highest_bits() {
+        assert_eq!(
+            FieldOp::SetTop(FloatField::Mantissa, 4).apply(0, false),
+            0x0078_0000
+        );
+        assert_eq!(
+            FieldOp::SetTop(FloatField::Exponent, 3).apply(0, true),
+            0x7000_0000_0000_0000
+        );
+    }
+
+    #[test]
+    fn set_top_handles_the_ends_of_the_range() {
+        let mask = 0x5555_5555_5555_5555;
+
+        for double_precision in [false, true] {
+            for field in FloatField::ALL {
+                let field_mask = field.mask(double_precision);
+                let len = field.len(double_precision);
+
+                let none = FieldOp::SetTop(field, 0).apply(mask, double_precision);
+                assert_eq!(none, mask & !field_mask);
+
+                let all = FieldOp::SetTop(field, len).apply(mask, double_precision);
+                assert_eq!(all, mask | field_mask);
+
+                // Asking for more bits than the field has sets the whole field
+                let more = FieldOp::SetTop(field, len + 1).apply(mask, double_precision);
+                assert_eq!(more, all);
+            }
+        }
+    }
+}
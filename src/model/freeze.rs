@@ -0,0 +1,80 @@
+use crate::dsp::{FREEZE_DIVISIONS, FreezeLength, defaults};
+
+use nih_plug::prelude::*;
+
+/// In the order of [`FREEZE_DIVISIONS`], presets store the index.
+#[derive(Enum, Debug, PartialEq, Clone, Copy)]
+pub enum FreezeDivision {
+    #[name = "1/1"]
+    Whole,
+    #[name = "1/2"]
+    Half,
+    #[name = "1/4"]
+    Quarter,
+    #[name = "1/8"]
+    Eighth,
+    #[name = "1/16"]
+    Sixteenth,
+    #[name = "1/32"]
+    ThirtySecond,
+}
+
+impl Default for FreezeDivision {
+    fn default() -> Self {
+        Self::from_index(defaults::FREEZE_DIVISION)
+    }
+}
+
+impl FreezeDivision {
+    pub fn beats(&self) -> f32 {
+        FREEZE_DIVISIONS[self.to_index()]
+    }
+}
+
+/// Holds the bits selected by the mask for a while, untouched by the mode, while the rest of the
+/// word gets transformed as usual. Synced periods start on the beat grid.
+#[derive(Params, Debug)]
+pub struct FreezeParams {
+    #[id = "freeze_on"]
+    pub enabled: BoolParam,
+
+    /// Hold time while the freeze is not synced to the host tempo.
+    #[id = "freeze_time"]
+    pub time: FloatParam,
+
+    #[id = "freeze_sync"]
+    pub sync: BoolParam,
+
+    #[id = "freeze_division"]
+    pub division: EnumParam<FreezeDivision>,
+}
+
+impl Default for FreezeParams {
+    fn default() -> Self {
+        Self {
+            enabled: BoolParam::new("Freeze", false),
+            time: FloatParam::new(
+                "Freeze Time",
+                defaults::FREEZE_TIME_MS,
+                FloatRange::Skewed {
+                    min: 0.1,
+                    max: 2000.0,
+                    factor: FloatRange::skew_factor(-2.0),
+                },
+            )
+            .with_unit(" ms")
+            .with_value_to_string(formatters::v2s_f32_rounded(1)),
+            sync: BoolParam::new("Freeze Sync", false),
+            division: EnumParam::new("Freeze Division", FreezeDivision::default()),
+        }
+    }
+}
+
+impl FreezeParams {
+    pub fn settings(&self) -> Option<FreezeLength> {
+        self.enabled.value().then(|| match self.sync.value() {
+            true => FreezeLength::Beats(self.division.value().beats()),
+            false => FreezeLength::Milliseconds(self.time.value()),
+        })
+    }
+}
@@ -0,0 +1,115 @@
+use crate::dsp::{self, Choice, GateSettings, defaults};
+
+use nih_plug::prelude::*;
+
+/// In the order of [`dsp::GateAction`]'s options, presets store the index.
+#[derive(Enum, Debug, PartialEq, Clone, Copy)]
+pub enum GateAction {
+    /// Let the unprocessed input through while the gate is closed.
+    Bypass,
+    /// Fade the output to silence while the gate is closed.
+    Mute,
+}
+
+impl Default for GateAction {
+    fn default() -> Self {
+        Self::from_index(dsp::GateAction::default().index())
+    }
+}
+
+impl From<GateAction> for dsp::GateAction {
+    fn from(value: GateAction) -> Self {
+        Self::from_index(value.to_index())
+    }
+}
+
+#[derive(Params, Debug)]
+pub struct GateParams {
+    #[id = "gate_on"]
+    pub enabled: BoolParam,
+
+    #[id = "gate_threshold"]
+    pub threshold: FloatParam,
+
+    /// How far below the threshold the level has to drop before an open gate closes again.
+    #[id = "gate_hysteresis"]
+    pub hysteresis: FloatParam,
+
+    #[id = "gate_attack"]
+    pub attack: FloatParam,
+
+    #[id = "gate_release"]
+    pub release: FloatParam,
+
+    #[id = "gate_action"]
+    pub action: EnumParam<GateAction>,
+
+    /// Key the gate from the auxiliary input instead of the main input.
+    #[id = "gate_sidechain"]
+    pub sidechain: BoolParam,
+}
+
+impl Default for GateParams {
+    fn default() -> Self {
+        Self {
+            enabled: BoolParam::new("Gate", false),
+            threshold: FloatParam::new(
+                "Gate Threshold",
+                defaults::GATE_THRESHOLD_DB,
+                FloatRange::Linear {
+                    min: -90.0,
+                    max: 0.0,
+                },
+            )
+            .with_unit(" dB")
+            .with_value_to_string(formatters::v2s_f32_rounded(1)),
+            hysteresis: FloatParam::new(
+                "Gate Hysteresis",
+                defaults::GATE_HYSTERESIS_DB,
+                FloatRange::Linear {
+                    min: 0.0,
+                    max: 24.0,
+                },
+            )
+            .with_unit(" dB")
+            .with_value_to_string(formatters::v2s_f32_rounded(1)),
+            attack: FloatParam::new(
+                "Gate Attack",
+                defaults::GATE_ATTACK_MS,
+                FloatRange::Skewed {
+                    min: 0.1,
+                    max: 50.0,
+                    factor: FloatRange::skew_factor(-2.0),
+                },
+            )
+            .with_unit(" ms")
+            .with_value_to_string(formatters::v2s_f32_rounded(1)),
+            release: FloatParam::new(
+                "Gate Release",
+                defaults::GATE_RELEASE_MS,
+                FloatRange::Skewed {
+                    min: 5.0,
+                    max: 2000.0,
+                    factor: FloatRange::skew_factor(-2.0),
+                },
+            )
+            .with_unit(" ms")
+            .with_value_to_string(formatters::v2s_f32_rounded(0)),
+            action: EnumParam::new("Gate Action", GateAction::default()),
+            sidechain: BoolParam::new("Gate Sidechain", false),
+        }
+    }
+}
+
+impl GateParams {
+    pub fn settings(&self) -> Option<GateSettings> {
+        self.enabled.value().then(|| GateSettings {
+            threshold_db: self.threshold.value(),
+            hysteresis_db: self.hysteresis.value(),
+            attack_ms: self.attack.value(),
+            release_ms: self.release.value(),
+            action: self.action.value().into(),
+            sidechain: self.sidechain.value(),
+        })
+    }
+}
@@ -0,0 +1,92 @@
+use crate::dsp::{self, Choice, GeneratorSettings, defaults};
+
+use nih_plug::prelude::*;
+
+/// In the order of [`dsp::Waveform`]'s options, presets store the index.
+#[derive(Enum, Debug, PartialEq, Clone, Copy)]
+pub enum Waveform {
+    Sine,
+    Saw,
+    Square,
+    #[name = "White Noise"]
+    WhiteNoise,
+    #[name = "Pink Noise"]
+    PinkNoise,
+    /// Sine sweep across the audible range. Ignores the frequency.
+    Sweep,
+}
+
+impl Default for Waveform {
+    fn default() -> Self {
+        Self::from_index(dsp::Waveform::default().index())
+    }
+}
+
+impl From<Waveform> for dsp::Waveform {
+    fn from(value: Waveform) -> Self {
+        Self::from_index(value.to_index())
+    }
+}
+
+/// Built-in test signal for auditioning masks without routing any audio in.
+#[derive(Params, Debug)]
+pub struct GeneratorParams {
+    #[id = "generator_on"]
+    pub enabled: BoolParam,
+
+    #[id = "generator_waveform"]
+    pub waveform: EnumParam<Waveform>,
+
+    #[id = "generator_frequency"]
+    pub frequency: FloatParam,
+
+    #[id = "generator_level"]
+    pub level: FloatParam,
+
+    /// Replace the input with the test signal instead of mixing it in.
+    #[id = "generator_replace"]
+    pub replace: BoolParam,
+}
+
+impl Default for GeneratorParams {
+    fn default() -> Self {
+        Self {
+            enabled: BoolParam::new("Generator", false),
+            waveform: EnumParam::new("Generator Waveform", Waveform::default()),
+            frequency: FloatParam::new(
+                "Generator Frequency",
+                defaults::GENERATOR_FREQUENCY,
+                FloatRange::Skewed {
+                    min: 20.0,
+                    max: 20_000.0,
+                    factor: FloatRange::skew_factor(-2.0),
+                },
+            )
+            .with_unit(" Hz")
+            .with_value_to_string(formatters::v2s_f32_rounded(1)),
+            level: FloatParam::new(
+                "Generator Level",
+                util::db_to_gain(defaults::GENERATOR_LEVEL_DB),
+                FloatRange::Skewed {
+                    min: util::db_to_gain(-60.0),
+                    max: util::db_to_gain(0.0),
+                    factor: FloatRange::gain_skew_factor(-60.0, 0.0),
+                },
+            )
+            .with_unit("dB")
+            .with_value_to_string(formatters::v2s_f32_gain_to_db(2)),
+            replace: BoolParam::new("Generator Replace", defaults::GENERATOR_REPLACE),
+        }
+    }
+}
+
+impl GeneratorParams {
+    pub fn settings(&self) -> Option<GeneratorSettings> {
+        self.enabled.value().then(|| GeneratorSettings {
+            waveform: self.waveform.value().into(),
+            frequency: self.frequency.value(),
+            level: self.level.value(),
+            replace: self.replace.value(),
+        })
+    }
+}
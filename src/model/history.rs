@@ -0,0 +1,81 @@
+use crate::dsp::{self, Choice, HistorySettings, MAX_HISTORY, defaults};
+
+use nih_plug::prelude::*;
+
+/// In the order of [`dsp::HistoryMode`]'s options, presets store the index.
+#[derive(Enum, Debug, PartialEq, Clone, Copy)]
+pub enum HistoryMode {
+    /// Use the sample from a fixed amount of frames ago.
+    Delayed,
+    /// Use the bits all samples since the last reset have in common.
+    AndAccumulator,
+    /// Use the bits any sample since the last reset had set.
+    OrAccumulator,
+}
+
+impl Default for HistoryMode {
+    fn default() -> Self {
+        Self::from_index(dsp::HistoryMode::default().index())
+    }
+}
+
+impl From<HistoryMode> for dsp::HistoryMode {
+    fn from(value: HistoryMode) -> Self {
+        Self::from_index(value.to_index())
+    }
+}
+
+/// Takes the mask from the signal's own past instead of only the mask bits. The mask bits then
+/// select which bits of the history get applied.
+#[derive(Params, Debug)]
+pub struct HistoryParams {
+    #[id = "history_on"]
+    pub enabled: BoolParam,
+
+    #[id = "history_mode"]
+    pub mode: EnumParam<HistoryMode>,
+
+    #[id = "history_delay"]
+    pub delay: IntParam,
+
+    /// Period after which the accumulators start over.
+    #[id = "history_reset"]
+    pub reset: IntParam,
+}
+
+impl Default for HistoryParams {
+    fn default() -> Self {
+        Self {
+            enabled: BoolParam::new("History", false),
+            mode: EnumParam::new("History Mode", HistoryMode::default()),
+            delay: IntParam::new(
+                "History Delay",
+                defaults::HISTORY_DELAY as i32,
+                IntRange::Linear {
+                    min: 1,
+                    max: MAX_HISTORY as i32,
+                },
+            )
+            .with_unit(" smp"),
+            reset: IntParam::new(
+                "History Reset",
+                defaults::HISTORY_RESET as i32,
+                IntRange::Linear {
+                    min: 1,
+                    max: MAX_HISTORY as i32,
+                },
+            )
+            .with_unit(" smp"),
+        }
+    }
+}
+
+impl HistoryParams {
+    pub fn settings(&self) -> Option<HistorySettings> {
+        self.enabled.value().then(|| HistorySettings {
+            mode: self.mode.value().into(),
+            delay: self.delay.value() as usize,
+            reset: self.reset.value() as usize,
+        })
+    }
+}
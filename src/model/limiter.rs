@@ -0,0 +1,54 @@
+use crate::dsp::{LimiterSettings, defaults};
+
+use nih_plug::prelude::*;
+
+#[derive(Params, Debug)]
+pub struct LimiterParams {
+    #[id = "limiter_on"]
+    pub enabled: BoolParam,
+
+    /// The highest true-peak level the output is allowed to reach.
+    #[id = "limiter_ceiling"]
+    pub ceiling: FloatParam,
+
+    #[id = "limiter_release"]
+    pub release: FloatParam,
+}
+
+impl Default for LimiterParams {
+    fn default() -> Self {
+        Self {
+            enabled: BoolParam::new("Limiter", false),
+            ceiling: FloatParam::new(
+                "Limiter Ceiling",
+                defaults::LIMITER_CEILING_DB,
+                FloatRange::Linear {
+                    min: -24.0,
+                    max: 0.0,
+                },
+            )
+            .with_unit(" dB")
+            .with_value_to_string(formatters::v2s_f32_rounded(1)),
+            release: FloatParam::new(
+                "Limiter Release",
+                defaults::LIMITER_RELEASE_MS,
+                FloatRange::Skewed {
+                    min: 10.0,
+                    max: 1000.0,
+                    factor: FloatRange::skew_factor(-1.0),
+                },
+            )
+            .with_unit(" ms")
+            .with_value_to_string(formatters::v2s_f32_rounded(0)),
+        }
+    }
+}
+
+impl LimiterParams {
+    pub fn settings(&self) -> Option<LimiterSettings> {
+        self.enabled.value().then(|| LimiterSettings {
+            ceiling_db: self.ceiling.value(),
+            release_ms: self.release.value(),
+        })
+    }
+}
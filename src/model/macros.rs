@@ -0,0 +1,88 @@
+use crate::dsp::{self, Choice, amount_mask, morph_mask};
+
+use crossbeam::atomic::AtomicCell;
+use nih_plug::prelude::*;
+use std::sync::Arc;
+
+/// In the order of [`dsp::BitOrder`]'s options, presets store the index.
+#[derive(Enum, Debug, PartialEq, Clone, Copy)]
+pub enum BitOrder {
+    /// Start at the least significant mantissa bit.
+    #[name = "Low First"]
+    LowFirst,
+    /// Start at the sign bit.
+    #[name = "High First"]
+    HighFirst,
+    /// Jump around the word in a fixed pattern.
+    Scattered,
+}
+
+impl Default for BitOrder {
+    fn default() -> Self {
+        Self::from_index(dsp::BitOrder::default().index())
+    }
+}
+
+impl From<BitOrder> for dsp::BitOrder {
+    fn from(value: BitOrder) -> Self {
+        Self::from_index(value.to_index())
+    }
+}
+
+/// Continuous controls that sweep through masks, so textures can be automated with one lane.
+#[derive(Params, Debug)]
+pub struct MacroParams {
+    /// The second mask the morph macro travels to. The mask bits are the first one.
+    #[persist = "mask-b"]
+    pub mask_b: Arc<AtomicCell<u64>>,
+
+    /// Enables this share of the word on top of the mask bits.
+    #[id = "macro_amount"]
+    pub amount: FloatParam,
+
+    #[id = "macro_amount_order"]
+    pub amount_order: EnumParam<BitOrder>,
+
+    #[id = "macro_morph"]
+    pub morph: FloatParam,
+
+    #[id = "macro_morph_order"]
+    pub morph_order: EnumParam<BitOrder>,
+}
+
+impl Default for MacroParams {
+    fn default() -> Self {
+        Self {
+            mask_b: Arc::new(AtomicCell::new(0)),
+            amount: FloatParam::new(
+                "Macro Amount",
+                0.0,
+                FloatRange::Linear { min: 0.0, max: 1.0 },
+            )
+            .with_unit("%")
+            .with_value_to_string(formatters::v2s_f32_percentage(0))
+            .with_string_to_value(formatters::s2v_f32_percentage()),
+            amount_order: EnumParam::new("Macro Amount Order", BitOrder::default()),
+            morph: FloatParam::new(
+                "Macro Morph",
+                0.0,
+                FloatRange::Linear { min: 0.0, max: 1.0 },
+            )
+            .with_unit("%")
+            .with_value_to_string(formatters::v2s_f32_percentage(0))
+            .with_string_to_value(formatters::s2v_f32_percentage()),
+            morph_order: EnumParam::new("Macro Morph Order", BitOrder::default()),
+        }
+    }
+}
+
+impl MacroParams {
+    /// The mask the macros turn the `width` bits wide mask `a` into.
+    pub fn apply(&self, a: u64, width: u32) -> u64 {
+        let b = self.mask_b.load();
+        let (morph_order, amount_order) = (self.morph_order.value(), self.amount_order.value());
+
+        let morphed = morph_mask(a, b, self.morph.value(), width, morph_order.into());
+        morphed | amount_mask(self.amount.value(), width, amount_order.into())
+    }
+}
@@ -1,5 +1,27 @@
 mod bits;
+mod decimator;
+mod fields;
+mod freeze;
+mod gate;
+mod generator;
+mod history;
+mod limiter;
+mod macros;
 mod modes;
+mod snapshot;
+mod transition;
+mod truncate;
 
 pub use bits::*;
+pub use decimator::*;
+pub use fields::*;
+pub use freeze::*;
+pub use gate::*;
+pub use generator::*;
+pub use history::*;
+pub use limiter::*;
+pub use macros::*;
 pub use modes::*;
+pub use snapshot::*;
+pub use transition::*;
+pub use truncate::*;
@@ -1,6 +1,9 @@
+use crate::dsp::Mode;
+
 use nih_plug::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Enum, Debug, PartialEq, Clone, Copy)]
+#[derive(Enum, Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
 pub enum FlipModes {
     And,
     Or,
@@ -14,16 +17,24 @@ impl Default for FlipModes {
     }
 }
 
-impl FlipModes {
-    pub fn transform(&self, sample: f32, mask: u32) -> f32 {
-        let bits = sample.to_bits();
-        let flipped = match self {
-            Self::And => bits & mask,
-            Self::Or => bits | mask,
-            Self::Not => !bits,
-            Self::Xor => bits ^ mask,
-        };
+impl From<FlipModes> for Mode {
+    fn from(value: FlipModes) -> Self {
+        match value {
+            FlipModes::And => Self::And,
+            FlipModes::Or => Self::Or,
+            FlipModes::Not => Self::Not,
+            FlipModes::Xor => Self::Xor,
+        }
+    }
+}
 
-        f32::from_bits(flipped).clamp(-1.0, 1.0)
+impl From<Mode> for FlipModes {
+    fn from(value: Mode) -> Self {
+        match value {
+            Mode::And => Self::And,
+            Mode::Or => Self::Or,
+            Mode::Not => Self::Not,
+            Mode::Xor => Self::Xor,
+        }
     }
 }
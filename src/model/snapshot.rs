@@ -0,0 +1,172 @@
+use crate::model::FlipModes;
+
+use nih_plug::params::persist::PersistentField;
+use nih_plug::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::sync::atomic::{self, AtomicU64, Ordering};
+
+pub const SNAPSHOT_SLOTS: usize = 8;
+
+/// A complete mask state, before the macros are applied.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub mask: u64,
+    pub mode: FlipModes,
+}
+
+/// The stored snapshots, read on the audio thread. A mask takes up a whole atomic, so the mode
+/// can't go in with it. Instead a sequence lock keeps the two together: readers never block, they
+/// read again when a store happened in the meantime.
+#[derive(Debug, Default)]
+pub struct SnapshotStore {
+    /// Odd while a store is in progress, bumped twice by every store.
+    version: AtomicU64,
+    masks: [AtomicU64; SNAPSHOT_SLOTS],
+    /// Byte `n` holds slot `n + 1`'s mode as its index plus one, or 0 when the slot is empty.
+    modes: AtomicU64,
+}
+
+impl SnapshotStore {
+    fn load_slot(&self, index: usize) -> Option<Snapshot> {
+        let (mask, modes) = loop {
+            let version = self.version.load(Ordering::Acquire);
+
+            if version % 2 == 1 {
+                std::hint::spin_loop();
+                continue;
+            }
+
+            let mask = self.masks[index].load(Ordering::Relaxed);
+            let modes = self.modes.load(Ordering::Relaxed);
+
+            atomic::fence(Ordering::Acquire);
+            if self.version.load(Ordering::Relaxed) == version {
+                break (mask, modes);
+            }
+        };
+
+        let mode = (modes >> (index * 8)) as u8;
+
+        Some(Snapshot {
+            mask,
+            mode: FlipModes::from_index(usize::from(mode.checked_sub(1)?)),
+        })
+    }
+
+    fn store_slot(&self, index: usize, snapshot: Option<Snapshot>) {
+        let shift = index * 8;
+        let mode = snapshot.map_or(0, |snapshot| snapshot.mode.to_index() as u64 + 1);
+
+        // The editor and the host restoring a state could both be storing
+        let version = loop {
+            let version = self.version.load(Ordering::Relaxed);
+
+            if version % 2 == 0
+                && self
+                    .version
+                    .compare_exchange_weak(
+                        version,
+                        version + 1,
+                        Ordering::Acquire,
+                        Ordering::Relaxed,
+                    )
+                    .is_ok()
+            {
+                break version;
+            }
+
+            std::hint::spin_loop();
+        };
+        atomic::fence(Ordering::Release);
+
+        if let Some(snapshot) = snapshot {
+            self.masks[index].store(snapshot.mask, Ordering::Relaxed);
+        }
+
+        let modes = self.modes.load(Ordering::Relaxed) & !(0xff << shift);
+        self.modes.store(modes | (mode << shift), Ordering::Relaxed);
+
+        self.version.store(version + 2, Ordering::Release);
+    }
+
+    fn to_array(&self) -> [Option<Snapshot>; SNAPSHOT_SLOTS] {
+        std::array::from_fn(|index| self.load_slot(index))
+    }
+}
+
+/// Saved as an array of slots, so states keep the layout they had before the store was lock-free.
+impl<'a> PersistentField<'a, [Option<Snapshot>; SNAPSHOT_SLOTS]> for Arc<SnapshotStore> {
+    fn set(&self, new_value: [Option<Snapshot>; SNAPSHOT_SLOTS]) {
+        for (index, snapshot) in new_value.into_iter().enumerate() {
+            self.store_slot(index, snapshot);
+        }
+    }
+
+    fn map<F, R>(&self, f: F) -> R
+    where
+        F: Fn(&[Option<Snapshot>; SNAPSHOT_SLOTS]) -> R,
+    {
+        f(&self.to_array())
+    }
+}
+
+/// Slots holding whole mask states, so one automation lane can switch between them.
+#[derive(Params, Debug)]
+pub struct SnapshotParams {
+    #[persist = "snapshots"]
+    pub slots: Arc<SnapshotStore>,
+
+    /// The recalled slot, counting from 1. At 0 the mask bits and mode are used as they are.
+    #[id = "snapshot"]
+    pub slot: IntParam,
+}
+
+impl Default for SnapshotParams {
+    fn default() -> Self {
+        Self {
+            slots: Arc::new(SnapshotStore::default()),
+            slot: IntParam::new(
+                "Snapshot",
+                0,
+                IntRange::Linear {
+                    min: 0,
+                    max: SNAPSHOT_SLOTS as i32,
+                },
+            )
+            .with_value_to_string(Arc::new(|value| match value {
+                0 => String::from("Off"),
+                slot => slot.to_string(),
+            }))
+            .with_string_to_value(Arc::new(|string| match string.trim() {
+                "Off" | "off" => Some(0),
+                slot => slot.parse().ok(),
+            })),
+        }
+    }
+}
+
+impl SnapshotParams {
+    /// The snapshot in the recalled slot. Recalling an empty slot leaves the mask bits in charge.
+    pub fn recalled(&self) -> Option<Snapshot> {
+        self.get(self.slot.value())
+    }
+
+    pub fn get(&self, slot: i32) -> Option<Snapshot> {
+        self.slots.load_slot(index(slot)?)
+    }
+
+    pub fn store(&self, slot: i32, snapshot: Snapshot) {
+        if let Some(index) = index(slot) {
+            self.slots.store_slot(index, Some(snapshot));
+        }
+    }
+}
+
+/// Slots count from 1, the index into the array from 0.
+fn index(slot: i32) -> Option<usize> {
+    usize::try_from(slot)
+        .ok()?
+        .checked_sub(1)
+        .filter(|index| *index < SNAPSHOT_SLOTS)
+}
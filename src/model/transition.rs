@@ -0,0 +1,57 @@
+use crate::dsp::{self, Choice, defaults};
+
+use nih_plug::prelude::*;
+
+/// In the order of [`dsp::TransitionStyle`]'s options, presets store the index.
+#[derive(Enum, Debug, PartialEq, Clone, Copy)]
+pub enum TransitionStyle {
+    /// Switch to the new mask instantly.
+    Off,
+    /// Linear crossfade between the old and the new transform result.
+    Crossfade,
+    /// Switch the changed bits over one at a time, starting from the least significant one.
+    BitMorph,
+    /// Equal-power crossfade between the old and the new transform result.
+    #[name = "Equal Power"]
+    EqualPower,
+}
+
+impl Default for TransitionStyle {
+    fn default() -> Self {
+        Self::from_index(dsp::TransitionStyle::default().index())
+    }
+}
+
+impl From<TransitionStyle> for dsp::TransitionStyle {
+    fn from(value: TransitionStyle) -> Self {
+        Self::from_index(value.to_index())
+    }
+}
+
+#[derive(Params, Debug)]
+pub struct TransitionParams {
+    #[id = "transition_style"]
+    pub style: EnumParam<TransitionStyle>,
+
+    #[id = "transition_time"]
+    pub time: FloatParam,
+}
+
+impl Default for TransitionParams {
+    fn default() -> Self {
+        Self {
+            style: EnumParam::new("Transition Style", TransitionStyle::default()),
+            time: FloatParam::new(
+                "Transition Time",
+                defaults::TRANSITION_TIME_MS,
+                FloatRange::Skewed {
+                    min: 1.0,
+                    max: 500.0,
+                    factor: FloatRange::skew_factor(-2.0),
+                },
+            )
+            .with_unit(" ms")
+            .with_value_to_string(formatters::v2s_f32_rounded(1)),
+        }
+    }
+}
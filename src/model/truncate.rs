@@ -0,0 +1,34 @@
+use crate::dsp::defaults;
+
+use nih_plug::prelude::*;
+
+#[derive(Params, Debug)]
+pub struct TruncateParams {
+    #[id = "truncate_on"]
+    pub enabled: BoolParam,
+
+    /// Number of mantissa bits that survive the truncation, out of 23.
+    #[id = "truncate_depth"]
+    pub depth: IntParam,
+}
+
+impl Default for TruncateParams {
+    fn default() -> Self {
+        Self {
+            enabled: BoolParam::new("Truncate", false),
+            depth: IntParam::new(
+                "Truncate Depth",
+                defaults::TRUNCATE_DEPTH as i32,
+                IntRange::Linear { min: 0, max: 23 },
+            )
+            .with_unit(" bits"),
+        }
+    }
+}
+
+impl TruncateParams {
+    /// The amount of mantissa bits to keep, if truncation is enabled.
+    pub fn settings(&self) -> Option<u32> {
+        self.enabled.value().then(|| self.depth.value() as u32)
+    }
+}
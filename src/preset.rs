@@ -0,0 +1,244 @@
+//! Presets, stored as JSON files in `<config dir>/bit_flipper/presets/`. The format itself is
+//! described in [`dsp::preset`], which the command line tool reads presets with as well.
+
+use crate::BitFlipperParams;
+use crate::dsp;
+use crate::model::FlipModes;
+use crate::settings::{UserSettings, config_dir};
+
+use nih_plug::prelude::*;
+use std::fmt;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+pub use dsp::preset::{PRESET_VERSION, Preset, USER_CATEGORY};
+
+const EXTENSION: &str = "json";
+
+/// Written to the presets folder the first time the editor opens.
+const FACTORY_BANK: &[(&str, &str)] = &[
+    ("Init", include_str!("../assets/presets/Init.json")),
+    ("Fizz", include_str!("../assets/presets/Fizz.json")),
+    ("Crusher", include_str!("../assets/presets/Crusher.json")),
+    (
+        "Octave Fold",
+        include_str!("../assets/presets/Octave Fold.json"),
+    ),
+    (
+        "Broken DAC",
+        include_str!("../assets/presets/Broken DAC.json"),
+    ),
+    ("Stutter", include_str!("../assets/presets/Stutter.json")),
+];
+
+/// Parameters that are stored in a dedicated field, or not at all.
+fn is_excluded(id: &str) -> bool {
+    id.starts_with("mask_bit_") || id == "mode" || id == "bypass" || id == "snapshot"
+}
+
+#[derive(Debug)]
+pub enum PresetError {
+    Io(std::io::Error),
+    /// The file isn't a preset this version can read.
+    Format(dsp::preset::PresetError),
+    /// Saving would replace the preset with this name.
+    AlreadyExists(String),
+}
+
+impl fmt::Display for PresetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "{err}"),
+            Self::Format(err) => write!(f, "{err}"),
+            Self::AlreadyExists(name) => write!(f, "A preset named \"{name}\" already exists."),
+        }
+    }
+}
+
+impl std::error::Error for PresetError {}
+
+impl From<std::io::Error> for PresetError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<dsp::preset::PresetError> for PresetError {
+    fn from(value: dsp::preset::PresetError) -> Self {
+        Self::Format(value)
+    }
+}
+
+/// Takes a snapshot of the current parameter values.
+pub fn capture(params: &BitFlipperParams, name: &str) -> Preset {
+    let mode = FlipModes::variants()[params.mode.value().to_index()];
+
+    let values = params
+        .param_map()
+        .into_iter()
+        .filter(|(id, _, _)| !is_excluded(id))
+        // SAFETY: The pointers point into `params`, which outlives this function
+        .map(|(id, ptr, _)| (id, unsafe { ptr.unmodulated_plain_value() }))
+        .collect();
+
+    Preset {
+        version: PRESET_VERSION,
+        name: name.to_string(),
+        category: USER_CATEGORY.to_string(),
+        mask: format!("{:#018x}", params.bits.to_u64()),
+        mode: mode.to_string(),
+        params: values,
+    }
+}
+
+pub fn load(path: &Path) -> Result<Preset, PresetError> {
+    Ok(Preset::from_json(&fs::read_to_string(path)?)?)
+}
+
+/// Writes the preset to the presets folder, named after the preset. A preset with the same name,
+/// factory ones included, only gets replaced when `overwrite` is set.
+pub fn save(preset: &Preset, overwrite: bool) -> Result<PathBuf, PresetError> {
+    let dir = presets_dir().ok_or_else(|| std::io::Error::from(std::io::ErrorKind::NotFound))?;
+    fs::create_dir_all(&dir)?;
+
+    let path = dir.join(format!("{}.{EXTENSION}", preset.name));
+    let json = preset.to_json()?;
+
+    let mut file = match fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .create_new(!overwrite)
+        .open(&path)
+    {
+        Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => {
+            return Err(PresetError::AlreadyExists(preset.name.clone()));
+        }
+        file => file?,
+    };
+    file.write_all(json.as_bytes())?;
+
+    Ok(path)
+}
+
+/// Sets every parameter to the preset's value as a single gesture per parameter, so the host can
+/// record the change.
+pub fn apply(
+    preset: &Preset,
+    params: &BitFlipperParams,
+    context: &dyn GuiContext,
+) -> Result<(), PresetError> {
+    // Parse everything first so a broken preset doesn't get half applied
+    let mask = preset.mask()?;
+    let mode = FlipModes::from(preset.mode()?);
+    let setter = ParamSetter::new(context);
+
+    for id in 1..=64u8 {
+        if let Some(param) = params.bits.get_bit_param(id) {
+            setter.begin_set_parameter(param);
+            setter.set_parameter(param, (mask >> (id - 1)) & 1 == 1);
+            setter.end_set_parameter(param);
+        }
+    }
+
+    setter.begin_set_parameter(&params.mode);
+    setter.set_parameter(&params.mode, mode);
+    setter.end_set_parameter(&params.mode);
+
+    for (id, ptr, _) in params.param_map() {
+        if is_excluded(&id) {
+            continue;
+        }
+
+        let normalized = normalized_value(preset, &id, ptr);
+
+        // SAFETY: The pointers point into `params`, which outlives this function
+        unsafe {
+            context.raw_begin_set_parameter(ptr);
+            context.raw_set_parameter_normalized(ptr, normalized);
+            context.raw_end_set_parameter(ptr);
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether the parameters still hold the values the preset would set.
+pub fn matches(preset: &Preset, params: &BitFlipperParams) -> bool {
+    let (Ok(mask), Ok(mode)) = (preset.mask(), preset.mode()) else {
+        return false;
+    };
+
+    if params.bits.to_u64() != mask || params.mode.value() != FlipModes::from(mode) {
+        return false;
+    }
+
+    params
+        .param_map()
+        .into_iter()
+        .filter(|(id, _, _)| !is_excluded(id))
+        .all(|(id, ptr, _)| {
+            // SAFETY: The pointers point into `params`, which outlives this function
+            let current = unsafe { ptr.unmodulated_normalized_value() };
+
+            (current - normalized_value(preset, &id, ptr)).abs() < 1e-4
+        })
+}
+
+/// The normalized value for a parameter, falling back to its default when the preset doesn't have
+/// it. The parameters' defaults are the ones in [`dsp::defaults`].
+fn normalized_value(preset: &Preset, id: &str, ptr: ParamPtr) -> f32 {
+    // SAFETY: Only called with pointers from `param_map()`, which point into the parameters
+    unsafe {
+        match preset.params.get(id) {
+            Some(plain) => ptr.preview_normalized(*plain),
+            None => ptr.default_normalized_value(),
+        }
+    }
+}
+
+pub fn presets_dir() -> Option<PathBuf> {
+    config_dir().map(|p| p.join("presets"))
+}
+
+/// Writes out the factory bank, leaving presets that already exist alone. Once every preset is
+/// written that's noted in the settings, after that the factory presets are the user's to change
+/// or delete.
+pub fn install_factory_bank() {
+    let Some(dir) = presets_dir() else {
+        return;
+    };
+
+    if fs::create_dir_all(&dir).is_err() {
+        return;
+    }
+
+    let installed = FACTORY_BANK.iter().all(|(name, json)| {
+        let path = dir.join(format!("{name}.{EXTENSION}"));
+
+        path.exists() || fs::write(path, json).is_ok()
+    });
+
+    if installed {
+        UserSettings::update(|s| s.factory_bank_installed = true).ok();
+    }
+}
+
+/// Every preset in the presets folder, sorted by category and name. Files that fail to load are skipped.
+pub fn list() -> Vec<(PathBuf, Preset)> {
+    let Some(entries) = presets_dir().and_then(|dir| fs::read_dir(dir).ok()) else {
+        return Vec::new();
+    };
+
+    let mut presets: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == EXTENSION))
+        .filter_map(|path| load(&path).ok().map(|preset| (path, preset)))
+        .collect();
+
+    presets.sort_by_cached_key(|(_, preset)| {
+        (preset.category.to_lowercase(), preset.name.to_lowercase())
+    });
+    presets
+}
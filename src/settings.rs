@@ -0,0 +1,98 @@
+//! Settings shared by every instance of the plugin, stored in `<config dir>/bit_flipper/settings.json`.
+//! Unlike the parameters they aren't saved with a project.
+//!
+//! ```json
+//! {
+//!   "hide_warning": false,
+//!   "ui_scale": 3,
+//!   "theme": null,
+//!   "animations": true,
+//!   "factory_bank_installed": true
+//! }
+//! ```
+//!
+//! Missing settings get their default value. The config folder can be moved with the
+//! `BIT_FLIPPER_CONFIG_DIR` environment variable, which is handy in sandboxes like Flatpak and
+//! Snap that would otherwise tuck it away somewhere in the host's own folder.
+
+use crate::UI_SCALE;
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+/// Overrides the config folder when set.
+pub const CONFIG_DIR_VAR: &str = "BIT_FLIPPER_CONFIG_DIR";
+
+const FILE_NAME: &str = "settings.json";
+
+/// Anything larger wouldn't fit most screens anyway.
+pub const MAX_UI_SCALE: u32 = 8;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UserSettings {
+    /// Skips the DC offset warning when the editor opens.
+    pub hide_warning: bool,
+    /// The size of the editor in new instances, in multiples of its 200x200 pixels.
+    pub ui_scale: u32,
+    /// A folder in `themes/` to take the textures from, instead of the config folder itself.
+    pub theme: Option<String>,
+    /// Whether the background moves.
+    pub animations: bool,
+    /// Set once the factory presets have been written to the presets folder, so the ones that get
+    /// deleted stay deleted.
+    pub factory_bank_installed: bool,
+}
+
+impl Default for UserSettings {
+    fn default() -> Self {
+        Self {
+            hide_warning: false,
+            ui_scale: UI_SCALE as u32,
+            theme: None,
+            animations: true,
+            factory_bank_installed: false,
+        }
+    }
+}
+
+impl UserSettings {
+    /// Falls back to the defaults when the file is missing or broken.
+    pub fn load() -> Self {
+        let settings = config_dir()
+            .and_then(|dir| fs::read_to_string(dir.join(FILE_NAME)).ok())
+            .and_then(|json| serde_json::from_str::<Self>(&json).ok())
+            .unwrap_or_default();
+
+        Self {
+            ui_scale: settings.ui_scale.clamp(1, MAX_UI_SCALE),
+            ..settings
+        }
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        let dir = config_dir().ok_or_else(|| std::io::Error::from(std::io::ErrorKind::NotFound))?;
+        fs::create_dir_all(&dir)?;
+
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+
+        fs::write(dir.join(FILE_NAME), json)
+    }
+
+    /// Changes a setting, keeping whatever other instances have changed in the meantime.
+    pub fn update(f: impl FnOnce(&mut Self)) -> std::io::Result<()> {
+        let mut settings = Self::load();
+        f(&mut settings);
+
+        settings.save()
+    }
+}
+
+/// Where the settings, theme and presets live.
+pub fn config_dir() -> Option<PathBuf> {
+    match std::env::var_os(CONFIG_DIR_VAR) {
+        Some(dir) if !dir.is_empty() => Some(PathBuf::from(dir)),
+        _ => dirs::config_dir().map(|p| p.join(env!("CARGO_PKG_NAME"))),
+    }
+}
@@ -0,0 +1,65 @@
+//! Keeps projects saved with older versions loading the way they sounded.
+//!
+//! The layout of the saved state has a version of its own, stored next to the parameters. When a
+//! parameter is renamed, removed or changes meaning, bump [`STATE_VERSION`] and add a migration
+//! that upgrades the previous version to [`MIGRATIONS`]. Adding a parameter needs neither, as long
+//! as its default leaves the sound alone. Parameters missing from a state keep their defaults.
+//!
+//! Every released version has a state in `tests/states/` that has to keep loading.
+
+use nih_plug::prelude::*;
+use std::collections::BTreeMap;
+
+/// The current version of the state layout.
+pub const STATE_VERSION: u32 = 1;
+
+/// The persisted field the version is stored in.
+pub const STATE_VERSION_KEY: &str = "state-version";
+
+/// Persisted fields that belong to the editor or the layout rather than to the sound.
+const NON_SOUND_FIELDS: &[&str] = &["editor-state", STATE_VERSION_KEY];
+
+/// Upgrades a state from the version at its index to the next one.
+const MIGRATIONS: &[fn(&mut PluginState)] = &[
+    // 0 -> 1: States from before the version was stored, up to 0.3.7. Those only had the 32 mask
+    // bits, the mode and the gain, which still work the same.
+    |_| {},
+];
+
+const _: () = assert!(MIGRATIONS.len() == STATE_VERSION as usize);
+
+/// The version a state was saved with. States without one predate versioning.
+pub fn version(state: &PluginState) -> u32 {
+    state
+        .fields
+        .get(STATE_VERSION_KEY)
+        .and_then(|version| version.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+/// Brings a state up to [`STATE_VERSION`]. States from newer versions are left alone, there is no
+/// way to know what changed.
+pub fn migrate(state: &mut PluginState) {
+    let version = version(state);
+
+    if version >= STATE_VERSION {
+        return;
+    }
+
+    for migration in &MIGRATIONS[version as usize..] {
+        migration(state);
+    }
+
+    state
+        .fields
+        .insert(STATE_VERSION_KEY.to_string(), STATE_VERSION.to_string());
+}
+
+/// The persisted fields that are part of the sound, like the snapshot slots and mask B. Restoring
+/// them with [`Params::deserialize_fields()`] leaves the editor alone.
+pub fn sound_fields(params: &dyn Params) -> BTreeMap<String, String> {
+    let mut fields = params.serialize_fields();
+    fields.retain(|key, _| !NON_SOUND_FIELDS.contains(&key.as_str()));
+
+    fields
+}
@@ -0,0 +1,50 @@
+//! Presets have to keep loading, both the ones the editor saves and the ones written by hand from
+//! the format described in `bit_flipper_core::preset`.
+
+use bit_flipper::BitFlipperParams;
+use bit_flipper::preset::{self, Preset};
+use nih_plug::prelude::*;
+use std::collections::HashSet;
+
+/// The example from the documentation of the preset format.
+fn documented_example() -> String {
+    let source = include_str!("../bit_flipper_core/src/preset.rs");
+
+    source
+        .lines()
+        .filter_map(|line| line.strip_prefix("//!"))
+        .map(|line| line.strip_prefix(' ').unwrap_or(line))
+        .skip_while(|line| *line != "```json")
+        .skip(1)
+        .take_while(|line| *line != "```")
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn captured_presets_survive_json() {
+    let params = BitFlipperParams::default();
+    let captured = preset::capture(&params, "Test");
+
+    let parsed = Preset::from_json(&captured.to_json().unwrap()).unwrap();
+
+    assert_eq!(parsed, captured);
+    assert!(preset::matches(&parsed, &params));
+}
+
+#[test]
+fn documented_format_parses() {
+    let json = documented_example();
+    let preset = Preset::from_json(&json).unwrap_or_else(|err| panic!("{err}\n{json}"));
+
+    let params = BitFlipperParams::default();
+    let ids: HashSet<_> = params
+        .param_map()
+        .into_iter()
+        .map(|(id, _, _)| id)
+        .collect();
+
+    for id in preset.params.keys() {
+        assert!(ids.contains(id), "parameter '{id}' doesn't exist");
+    }
+}
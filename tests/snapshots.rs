@@ -0,0 +1,47 @@
+//! The audio thread reads snapshot slots while the editor stores them, it must never see the mask
+//! of one snapshot with the mode of another.
+
+use bit_flipper::BitFlipperParams;
+use bit_flipper::model::{FlipModes, Snapshot};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+
+const A: Snapshot = Snapshot {
+    mask: u64::MAX,
+    mode: FlipModes::And,
+};
+
+const B: Snapshot = Snapshot {
+    mask: 0x0000_0000_ffff_0000,
+    mode: FlipModes::Xor,
+};
+
+#[test]
+fn slots_are_never_read_half_stored() {
+    let params = Arc::new(BitFlipperParams::default());
+    let done = Arc::new(AtomicBool::new(false));
+
+    params.snapshot.store(1, A);
+
+    let writer = thread::spawn({
+        let params = params.clone();
+        let done = done.clone();
+
+        move || {
+            for n in 0..100_000 {
+                params.snapshot.store(1, if n % 2 == 0 { B } else { A });
+            }
+
+            done.store(true, Ordering::Relaxed);
+        }
+    });
+
+    while !done.load(Ordering::Relaxed) {
+        let snapshot = params.snapshot.get(1).expect("the slot was stored before");
+
+        assert!(snapshot == A || snapshot == B, "torn read: {snapshot:?}");
+    }
+
+    writer.join().unwrap();
+}
@@ -0,0 +1,212 @@
+//! Every released version's saved state has to keep loading. When releasing, save a project with
+//! the plugin, export its state as JSON and add it to `tests/states/` named after the version.
+//! States of a layout that hasn't been released yet are named after the version they'll ship in and
+//! their layout, and get renamed once that release is out.
+
+use bit_flipper::state::{STATE_VERSION, STATE_VERSION_KEY, migrate};
+use bit_flipper::{BitFlipper, BitFlipperParams};
+use nih_plug::prelude::*;
+use nih_plug::wrapper::state::ParamValue;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+fn corpus() -> Vec<(PathBuf, PluginState)> {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/states");
+
+    let states: Vec<_> = fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .map(|path| {
+            let json = fs::read_to_string(&path).unwrap();
+            let state = serde_json::from_str(&json)
+                .unwrap_or_else(|err| panic!("{}: {err}", path.display()));
+
+            (path, state)
+        })
+        .collect();
+
+    assert!(!states.is_empty(), "the state corpus is empty");
+    states
+}
+
+#[test]
+fn saved_states_migrate_to_the_current_version() {
+    for (path, mut state) in corpus() {
+        BitFlipper::filter_state(&mut state);
+
+        assert_eq!(
+            state.fields.get(STATE_VERSION_KEY),
+            Some(&STATE_VERSION.to_string()),
+            "{}",
+            path.display()
+        );
+    }
+}
+
+#[test]
+fn saved_states_only_refer_to_existing_parameters() {
+    for (path, mut state) in corpus() {
+        BitFlipper::filter_state(&mut state);
+
+        let params = BitFlipper::default().params();
+        let param_map: HashMap<_, _> = params
+            .param_map()
+            .into_iter()
+            .map(|(id, ptr, _)| (id, ptr))
+            .collect();
+
+        for (id, value) in &state.params {
+            let Some(ptr) = param_map.get(id) else {
+                panic!("{}: parameter '{id}' no longer exists", path.display());
+            };
+
+            let compatible = match ptr {
+                ParamPtr::BoolParam(_) => matches!(value, ParamValue::Bool(_)),
+                ParamPtr::EnumParam(_) => !matches!(value, ParamValue::Bool(_)),
+                _ => matches!(value, ParamValue::F32(_) | ParamValue::I32(_)),
+            };
+
+            assert!(compatible, "{}: '{id}' can't be {value:?}", path.display());
+        }
+    }
+}
+
+#[test]
+fn saved_states_restore_their_fields() {
+    for (path, mut state) in corpus() {
+        BitFlipper::filter_state(&mut state);
+
+        let params = BitFlipper::default().params();
+        let known = params.serialize_fields();
+
+        for key in state.fields.keys() {
+            assert!(
+                known.contains_key(key),
+                "{}: field '{key}' no longer exists",
+                path.display()
+            );
+        }
+
+        params.deserialize_fields(&state.fields);
+
+        let restored = params.serialize_fields();
+        for (key, value) in &state.fields {
+            assert_eq!(&restored[key], value, "{}: field '{key}'", path.display());
+        }
+    }
+}
+
+#[test]
+fn current_states_are_not_migrated() {
+    let params = BitFlipper::default().params();
+    let mut state = PluginState {
+        version: String::from(BitFlipper::VERSION),
+        params: Default::default(),
+        fields: params.serialize_fields(),
+    };
+
+    let before = state.fields.clone();
+    migrate(&mut state);
+
+    assert_eq!(state.fields, before);
+}
+
+/// The parts of a state that make up its sound in every version: the mask, the mode, the gain and
+/// the precision. Choices are the index of the option.
+#[derive(Debug)]
+struct Sound {
+    mask: u64,
+    mode: f32,
+    pre_gain: f32,
+    double_precision: bool,
+}
+
+impl Sound {
+    fn read(plain: impl Fn(&str) -> Option<f32>, mask_ids: &[String], ids: [&str; 3]) -> Self {
+        let [mode, pre_gain, double_precision] = ids;
+
+        let mask = mask_ids
+            .iter()
+            .enumerate()
+            .filter(|(_, id)| plain(id).is_some_and(|value| value >= 0.5))
+            .fold(0, |mask, (bit, _)| mask | 1 << bit);
+
+        Self {
+            mask,
+            mode: plain(mode).unwrap_or(0.0),
+            pre_gain: plain(pre_gain).unwrap_or(1.0),
+            double_precision: plain(double_precision).is_some_and(|value| value >= 0.5),
+        }
+    }
+}
+
+fn plain_value(value: &ParamValue) -> f32 {
+    match value {
+        ParamValue::F32(value) => *value,
+        ParamValue::I32(value) => *value as f32,
+        ParamValue::Bool(value) => f32::from(u8::from(*value)),
+        ParamValue::String(id) => panic!("choice '{id}' stored by name"),
+    }
+}
+
+#[test]
+fn saved_states_keep_their_sound() {
+    for (path, mut state) in corpus() {
+        // The parameters as they were called in every release so far
+        let mask_ids: Vec<_> = (1..=64).map(|n| format!("mask_bit_{n}")).collect();
+        let saved = Sound::read(
+            |id| state.params.get(id).map(plain_value),
+            &mask_ids,
+            ["mode", "pre_gain", "double_precision"],
+        );
+
+        BitFlipper::filter_state(&mut state);
+
+        // Looked up through the fields they set now, the way the host restores them
+        let params = BitFlipperParams::default();
+        let param_map: HashMap<_, _> = params
+            .param_map()
+            .into_iter()
+            .map(|(id, ptr, _)| (id, ptr))
+            .collect();
+        let id_of = |ptr: ParamPtr| {
+            let (id, _) = param_map.iter().find(|(_, other)| **other == ptr).unwrap();
+            id.as_str()
+        };
+
+        let mask_ids: Vec<_> = (1..=64)
+            .map(|n| id_of(params.bits.get_bit_param(n).unwrap().as_ptr()).to_string())
+            .collect();
+        let restored = Sound::read(
+            |id| {
+                let ptr = param_map.get(id)?;
+                let value = plain_value(state.params.get(id)?);
+
+                // SAFETY: `params` outlives the pointers
+                Some(unsafe { ptr.preview_plain(ptr.preview_normalized(value)) })
+            },
+            &mask_ids,
+            [
+                id_of(params.mode.as_ptr()),
+                id_of(params.pre_gain.as_ptr()),
+                id_of(params.double_precision.as_ptr()),
+            ],
+        );
+
+        let path = path.display();
+        assert_eq!(restored.mask, saved.mask, "{path}: mask");
+        assert_eq!(restored.mode, saved.mode, "{path}: mode");
+        assert!(
+            (restored.pre_gain - saved.pre_gain).abs() <= saved.pre_gain * 1e-5,
+            "{path}: pre-gain {} became {}",
+            saved.pre_gain,
+            restored.pre_gain
+        );
+        assert_eq!(
+            restored.double_precision, saved.double_precision,
+            "{path}: double precision"
+        );
+    }
+}